@@ -0,0 +1,64 @@
+use advanced_collections::circular_buffer::CircularBuffer;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::collections::VecDeque;
+
+const SIZES: [usize; 3] = [16, 1024, 65536];
+
+fn push_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circular_buffer/push_pop");
+    for &size in SIZES.iter() {
+        group.bench_with_input(BenchmarkId::new("push_back_full", size), &size, |b, &size| {
+            let mut buf = CircularBuffer::new(size);
+            buf.extend(0..size as u64);
+            let mut i = 0u64;
+            b.iter(|| {
+                i += 1;
+                buf.push_back(black_box(i));
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("push_back_pop_front", size), &size, |b, &size| {
+            let mut buf = CircularBuffer::new(size);
+            buf.extend(0..size as u64 / 2);
+            let mut i = 0u64;
+            b.iter(|| {
+                i += 1;
+                buf.push_back(black_box(i));
+                black_box(buf.pop_front())
+            });
+        });
+    }
+    group.finish();
+}
+
+fn extend(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circular_buffer/extend");
+    for &size in SIZES.iter() {
+        let data: Vec<u64> = (0..size as u64 * 2).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter(|| {
+                let mut buf = CircularBuffer::new(size);
+                buf.extend(data.iter().cloned());
+                buf
+            });
+        });
+    }
+    group.finish();
+}
+
+fn into_vec_deque(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circular_buffer/into_vec_deque");
+    for &size in SIZES.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(|| {
+                //a wrapped buffer
+                let mut buf = CircularBuffer::new(size);
+                buf.extend(0..size as u64 * 3 / 2);
+                buf
+            }, VecDeque::from, BatchSize::SmallInput);
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push_pop, extend, into_vec_deque);
+criterion_main!(benches);
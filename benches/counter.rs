@@ -0,0 +1,44 @@
+use advanced_collections::counter::Counter;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::iter::FromIterator;
+
+//deterministic, skewed data: small values are much more common than large ones
+fn data(len: usize, distinct: u64) -> Vec<u64> {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    (0..len).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let a = state % distinct;
+        let b = (state >> 32) % distinct;
+        a.min(b)
+    }).collect()
+}
+
+fn from_iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("counter/from_iter");
+    for &distinct in [16u64, 1024, 65536].iter() {
+        let values = data(100_000, distinct);
+        group.bench_with_input(BenchmarkId::from_parameter(distinct), &values, |b, values| {
+            b.iter(|| Counter::<u64>::from_iter(values.iter().cloned()));
+        });
+    }
+    group.finish();
+}
+
+fn most_common(c: &mut Criterion) {
+    let mut group = c.benchmark_group("counter/most_common");
+    for &distinct in [16u64, 1024, 65536].iter() {
+        let counter = Counter::<u64>::from_iter(data(100_000, distinct));
+        group.bench_with_input(BenchmarkId::new("all", distinct), &counter, |b, counter| {
+            b.iter(|| counter.most_common());
+        });
+        group.bench_with_input(BenchmarkId::new("ref_n_10", distinct), &counter, |b, counter| {
+            b.iter(|| counter.most_common_ref_n(10));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, from_iter, most_common);
+criterion_main!(benches);
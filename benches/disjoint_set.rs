@@ -0,0 +1,54 @@
+use advanced_collections::disjoint_set::DisjointSet;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+//deterministic pseudo-random pairs of elements from 0..size
+fn pairs(size: usize) -> Vec<(usize, usize)> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % size as u64) as usize
+    };
+    (0..size).map(|_| (next(), next())).collect()
+}
+
+fn union(c: &mut Criterion) {
+    let mut group = c.benchmark_group("disjoint_set/union");
+    group.sample_size(10);
+    for &size in SIZES.iter() {
+        let pairs = pairs(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &pairs, |b, pairs| {
+            b.iter(|| {
+                let mut ds: DisjointSet<usize> = DisjointSet::with_capacity(pairs.len());
+                for &(x, y) in pairs.iter() {
+                    ds.union(x, y);
+                }
+                ds
+            });
+        });
+    }
+    group.finish();
+}
+
+fn find(c: &mut Criterion) {
+    let mut group = c.benchmark_group("disjoint_set/in_union");
+    group.sample_size(10);
+    for &size in SIZES.iter() {
+        let pairs = pairs(size);
+        let mut ds: DisjointSet<usize> = DisjointSet::with_capacity(size);
+        //a long chain is the worst case for finding roots before paths get compressed
+        for i in 1..size {
+            ds.union(i - 1, i);
+        }
+        group.bench_with_input(BenchmarkId::from_parameter(size), &pairs, |b, pairs| {
+            b.iter(|| pairs.iter().filter(|&&(x, y)| ds.in_union(&x, &y)).count());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, union, find);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+use advanced_collections::interval::{Interval, IntervalSet};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::iter::FromIterator;
+
+//a set of `count` disjoint intervals with the given period
+fn set(count: u64, period: u64, offset: u64) -> IntervalSet<u64> {
+    IntervalSet::from_iter((0..count).map(|i| Interval::lower_closed(i * period + offset, i * period + offset + period / 2)))
+}
+
+fn set_operations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("interval_set");
+    for &count in [10u64, 1_000, 100_000].iter() {
+        let a = set(count, 10, 0);
+        let b = set(count, 14, 3);
+        group.bench_with_input(BenchmarkId::new("union", count), &count, |bench, _| {
+            bench.iter(|| a.union(&b));
+        });
+        group.bench_with_input(BenchmarkId::new("intersection", count), &count, |bench, _| {
+            bench.iter(|| a.intersection(&b));
+        });
+        group.bench_with_input(BenchmarkId::new("difference", count), &count, |bench, _| {
+            bench.iter(|| a.difference(&b));
+        });
+        group.bench_with_input(BenchmarkId::new("contains_val", count), &count, |bench, &count| {
+            bench.iter(|| (0..1000).filter(|i| a.contains_val(&(i * count / 100))).count());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, set_operations);
+criterion_main!(benches);
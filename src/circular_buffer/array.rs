@@ -0,0 +1,639 @@
+use core::fmt;
+use core::iter::{Extend, FromIterator, IntoIterator};
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Index, IndexMut};
+use core::marker::PhantomData;
+use core::ptr;
+use core::slice;
+
+use super::iter::{Iter, IterMut};
+use super::storage::Storage;
+
+/**
+Circular buffer working on memory provided by a `Storage`.
+
+The capacity of the buffer is the number of slots of the storage. The buffer never allocates
+memory on its own, so the same implementation works on arrays (see `ArrayCircularBuffer`),
+borrowed memory and boxed slices. When the buffer is full, pushing a new element removes
+an element from the opposite end of the buffer, the same way as in `CircularBuffer`.
+
+`CircularBuffer` is built on top of this buffer with a boxed slice as the storage. It manages
+the memory itself and additionally supports resizing, growing, eviction policies, sequence numbers
+and eviction callbacks. `GenericCircularBuffer` provides only the basic buffer operations.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::GenericCircularBuffer;
+use std::mem::MaybeUninit;
+
+fn main(){
+    //memory provided by the caller, e.g. a DMA buffer or an arena
+    let mut memory = [MaybeUninit::<u8>::uninit(); 4];
+    let mut cb = GenericCircularBuffer::from_storage(&mut memory[..]);
+    cb.extend(1..7);
+    assert_eq!(cb.capacity(), 4);
+    assert_eq!(cb.slices(), (&[3, 4][..], &[5, 6][..]));
+}
+```
+*/
+pub struct GenericCircularBuffer<T, S> where S: Storage<T> {
+    storage: S,
+    //number of slots of the storage, kept so that capacity() can be const for every storage
+    capacity: usize,
+    start: usize,
+    len: usize,
+    marker: PhantomData<T>
+}
+
+/**
+Circular buffer with a capacity known at compile time that stores elements inline.
+
+It does not allocate any memory on the heap, so it can be placed on the stack,
+in a static variable or used in `no_std` environments.
+Its behavior is the same as behavior of `CircularBuffer` - when the buffer is full,
+pushing a new element removes an element from the opposite end of the buffer.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::ArrayCircularBuffer;
+
+fn main(){
+    let mut cb: ArrayCircularBuffer<i32, 3> = ArrayCircularBuffer::new();
+    cb.push_back(1);
+    cb.push_back(2);
+    cb.push_back(3);
+    cb.push_back(4);
+    assert_eq!(cb.pop_front(), Some(2));
+    assert_eq!(cb.iter().collect::<Vec<_>>(), vec![&3, &4]);
+}
+```
+*/
+pub type ArrayCircularBuffer<T, const N: usize> = GenericCircularBuffer<T, [MaybeUninit<T>; N]>;
+
+impl<T, const N: usize> GenericCircularBuffer<T, [MaybeUninit<T>; N]> {
+    /**
+    Creates a new, empty buffer stored in an array.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::ArrayCircularBuffer;
+
+    static EMPTY: ArrayCircularBuffer<u8, 4> = ArrayCircularBuffer::new();
+
+    fn main(){
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.capacity(), 4);
+    }
+    ```
+    */
+    pub const fn new() -> Self {
+        Self {
+            storage: [const { MaybeUninit::uninit() }; N],
+            capacity: N,
+            start: 0,
+            len: 0,
+            marker: PhantomData
+        }
+    }
+}
+
+impl<T, S> GenericCircularBuffer<T, S> where S: Storage<T> {
+    /**
+    Creates a new, empty buffer using all slots of the storage.
+
+    Content of the storage is ignored, slots are treated as uninitialized.
+    */
+    pub fn from_storage(storage: S) -> Self {
+        Self {
+            capacity: storage.slots().len(),
+            storage,
+            start: 0,
+            len: 0,
+            marker: PhantomData
+        }
+    }
+
+    /**
+    Drops all elements of the buffer and gives the storage back.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::GenericCircularBuffer;
+    use std::mem::MaybeUninit;
+
+    fn main(){
+        let storage: Box<[MaybeUninit<i32>]> = (0..3).map(|_| MaybeUninit::uninit()).collect();
+        let mut cb = GenericCircularBuffer::from_storage(storage);
+        cb.push_back(1);
+        let storage = cb.into_storage();
+        assert_eq!(storage.len(), 3);
+    }
+    ```
+    */
+    pub fn into_storage(self) -> S {
+        let mut this = ManuallyDrop::new(self);
+        this.clear();
+        //the buffer is not dropped, so the storage is moved out only once
+        unsafe { ptr::read(&this.storage) }
+    }
+
+    /**
+    Returns maximal number of elements that can be stored in the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::ArrayCircularBuffer;
+
+    const fn free_slots<const N: usize>(cb: &ArrayCircularBuffer<u8, N>) -> usize {
+        cb.capacity() - cb.len()
+    }
+
+    fn main(){
+        let mut cb: ArrayCircularBuffer<u8, 4> = ArrayCircularBuffer::new();
+        cb.push_back(1);
+        assert_eq!(free_slots(&cb), 3);
+    }
+    ```
+    */
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    ///Returns current number of elements in the buffer.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    ///Checks if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Checks if the buffer is full.
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    /**
+    Places an element at the end of the buffer.
+
+    If the buffer is full, it replaces an element from the front of the buffer.
+    */
+    pub fn push_back(&mut self, val: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.is_full() {
+            self.pop_front();
+        }
+        let idx = self.physical_index(self.len);
+        self.storage.slots_mut()[idx] = MaybeUninit::new(val);
+        self.len += 1;
+    }
+
+    /**
+    Places an element at the beginning of the buffer.
+
+    If the buffer is full, it replaces an element from the back of the buffer.
+    */
+    pub fn push_front(&mut self, val: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.is_full() {
+            self.pop_back();
+        }
+        self.start = if self.start == 0 { self.capacity - 1 } else { self.start - 1 };
+        self.storage.slots_mut()[self.start] = MaybeUninit::new(val);
+        self.len += 1;
+    }
+
+    /**
+    Places an element at the end of the buffer if there is space for it.
+
+    If the buffer is full, the element is given back as an error.
+    */
+    pub fn try_push_back(&mut self, val: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(val);
+        }
+        self.push_back(val);
+        Ok(())
+    }
+
+    /**
+    Places an element at the beginning of the buffer if there is space for it.
+
+    If the buffer is full, the element is given back as an error.
+    */
+    pub fn try_push_front(&mut self, val: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(val);
+        }
+        self.push_front(val);
+        Ok(())
+    }
+
+    ///Pops an element from the end of the buffer.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let idx = self.physical_index(self.len);
+        //the slot is outside of the initialized region now, so it is read only once
+        Some(unsafe { self.storage.slots()[idx].assume_init_read() })
+    }
+
+    ///Pops an element from the beginning of the buffer.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let idx = self.start;
+        self.start = if self.start + 1 == self.capacity { 0 } else { self.start + 1 };
+        self.len -= 1;
+        //the slot is outside of the initialized region now, so it is read only once
+        Some(unsafe { self.storage.slots()[idx].assume_init_read() })
+    }
+
+    ///Clears content of the buffer.
+    pub fn clear(&mut self) {
+        let (a, b) = self.slices_mut();
+        let (a, b) = (a as *mut [T], b as *mut [T]);
+        self.start = 0;
+        self.len = 0;
+        //length is reset first, so a panicking destructor cannot cause a double drop
+        unsafe {
+            ptr::drop_in_place(a);
+            ptr::drop_in_place(b);
+        }
+    }
+
+    ///Returns a reference to the first element of the buffer.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    ///Returns a mutable reference to the first element of the buffer.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    ///Returns a reference to the last element of the buffer.
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    ///Returns a mutable reference to the last element of the buffer.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+
+    ///Returns a reference to the element at the given index or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let idx = self.physical_index(index);
+        Some(unsafe { self.storage.slots()[idx].assume_init_ref() })
+    }
+
+    ///Returns a mutable reference to the element at the given index or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let idx = self.physical_index(index);
+        Some(unsafe { self.storage.slots_mut()[idx].assume_init_mut() })
+    }
+
+    ///Returns an iterator over the buffer from the front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (a, b) = self.slices();
+        Iter::new(a, b)
+    }
+
+    ///Returns a mutable iterator over the buffer from the front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (a, b) = self.slices_mut();
+        IterMut::new(a, b)
+    }
+
+    /**
+    Returns a pair of slices which contain, in order, the contents of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::ArrayCircularBuffer;
+
+    fn main(){
+        let mut cb: ArrayCircularBuffer<i32, 3> = ArrayCircularBuffer::new();
+        cb.extend(1..5);
+        assert_eq!(cb.slices(), (&[2, 3][..], &[4][..]));
+    }
+    ```
+    */
+    pub fn slices(&self) -> (&[T], &[T]) {
+        let (first, second) = self.ranges();
+        let ptr = self.storage.slots().as_ptr() as *const T;
+        //both ranges contain only initialized elements
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(first.0), first.1),
+                slice::from_raw_parts(ptr, second)
+            )
+        }
+    }
+
+    ///Returns a pair of mutable slices which contain, in order, the contents of the buffer.
+    pub fn slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+        let (first, second) = self.ranges();
+        let ptr = self.storage.slots_mut().as_mut_ptr() as *mut T;
+        //both ranges contain only initialized elements and do not overlap
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(first.0), first.1),
+                slice::from_raw_parts_mut(ptr, second)
+            )
+        }
+    }
+
+//private helpers
+
+    fn physical_index(&self, index: usize) -> usize {
+        //start + index could overflow for capacities close to usize::MAX, e.g. of zero-sized types
+        let to_end = self.capacity - self.start;
+        if index < to_end { self.start + index } else { index - to_end }
+    }
+
+    //(start, length) of the first region and length of the second one starting at 0
+    fn ranges(&self) -> ((usize, usize), usize) {
+        let to_end = self.capacity - self.start;
+        if self.len <= to_end {
+            ((self.start, self.len), 0)
+        } else {
+            ((self.start, to_end), self.len - to_end)
+        }
+    }
+}
+
+//operations used by `CircularBuffer`, which is built on top of this buffer
+#[cfg(feature = "std")]
+impl<T, S> GenericCircularBuffer<T, S> where S: Storage<T> {
+    /*
+    Creates a buffer using the storage with the first `len` slots already initialized.
+
+    Safety: the first `len` slots of the storage need to be initialized.
+    */
+    pub(super) unsafe fn from_initialized(storage: S, len: usize) -> Self {
+        let mut res = Self::from_storage(storage);
+        debug_assert!(len <= res.capacity);
+        res.len = len;
+        res
+    }
+
+    //position of the front in the storage
+    pub(super) fn start(&self) -> usize {
+        self.start
+    }
+
+    /*
+    Removes `n` elements from the front without dropping them.
+
+    Safety: the elements need to be moved out before or must not need to be dropped.
+    */
+    pub(super) unsafe fn forget_front(&mut self, n: usize) {
+        assert!(n <= self.len);
+        if n > 0 {
+            self.start = self.physical_index(n);
+            self.len -= n;
+        }
+    }
+
+    //free slots after the back, the second slice continues from the beginning of the storage
+    pub(super) fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let free = self.capacity - self.len;
+        let back = if free == 0 { 0 } else { self.physical_index(self.len) };
+        let first_len = free.min(self.capacity - back);
+        let (head, tail) = self.storage.slots_mut().split_at_mut(back);
+        (&mut tail[..first_len], &mut head[..free - first_len])
+    }
+
+    /*
+    Appends `n` elements written into the slots returned by `spare_capacity_mut()`.
+
+    Safety: the first `n` free slots need to be initialized.
+    */
+    pub(super) unsafe fn commit(&mut self, n: usize) {
+        assert!(n <= self.capacity - self.len);
+        self.len += n;
+    }
+
+    //panics if any index is out of bounds
+    pub(super) fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.len && b < self.len, "Index outside of bound of CircularBuffer");
+        let (a, b) = (self.physical_index(a), self.physical_index(b));
+        self.storage.slots_mut().swap(a, b);
+    }
+
+    //moves the front to the beginning of the storage, rotating all slots
+    pub(super) fn linearize(&mut self) -> &mut [T] {
+        let start = self.start;
+        self.storage.slots_mut().rotate_left(start);
+        self.start = 0;
+        self.slices_mut().0
+    }
+}
+
+impl<T, S> Drop for GenericCircularBuffer<T, S> where S: Storage<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for ArrayCircularBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Clone for ArrayCircularBuffer<T, N> where T: Clone {
+    fn clone(&self) -> Self {
+        let mut res = Self::new();
+        res.extend(self.iter().cloned());
+        res
+    }
+}
+
+impl<T, S> fmt::Debug for GenericCircularBuffer<T, S> where S: Storage<T>, T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S1, S2> PartialEq<GenericCircularBuffer<T, S2>> for GenericCircularBuffer<T, S1>
+    where S1: Storage<T>, S2: Storage<T>, T: PartialEq
+{
+    fn eq(&self, other: &GenericCircularBuffer<T, S2>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T, S> Eq for GenericCircularBuffer<T, S> where S: Storage<T>, T: Eq {}
+
+impl<T, S> Index<usize> for GenericCircularBuffer<T, S> where S: Storage<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &<Self as Index<usize>>::Output {
+        self.get(index).expect("Index outside of bound of GenericCircularBuffer")
+    }
+}
+
+impl<T, S> IndexMut<usize> for GenericCircularBuffer<T, S> where S: Storage<T> {
+    fn index_mut(&mut self, index: usize) -> &mut <Self as Index<usize>>::Output {
+        self.get_mut(index).expect("Index outside of bound of GenericCircularBuffer")
+    }
+}
+
+impl<T, S> Extend<T> for GenericCircularBuffer<T, S> where S: Storage<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for el in iter {
+            self.push_back(el);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for ArrayCircularBuffer<T, N> {
+    ///Creates a buffer with the last N elements of the iterator.
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut res = Self::new();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a GenericCircularBuffer<T, S> where S: Storage<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a mut GenericCircularBuffer<T, S> where S: Storage<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop(){
+        let mut cb: ArrayCircularBuffer<i32, 3> = ArrayCircularBuffer::new();
+        assert_eq!(cb.pop_front(), None);
+        cb.push_back(1);
+        cb.push_front(0);
+        cb.push_back(2);
+        cb.push_back(3);
+        assert_eq!(cb.slices(), (&[1, 2, 3][..], &[][..]));
+        cb.push_front(-1);
+        assert_eq!(cb.iter().cloned().collect::<Vec<_>>(), vec![-1, 1, 2]);
+        assert_eq!(cb.try_push_back(4), Err(4));
+        assert_eq!(cb.pop_back(), Some(2));
+        assert_eq!(cb.try_push_front(-2), Ok(()));
+        assert_eq!(cb[0], -2);
+        cb[2] = 10;
+        assert_eq!(cb.last(), Some(&10));
+        assert_eq!(cb.first(), Some(&-2));
+        for x in &mut cb {
+            *x *= 2;
+        }
+        assert_eq!(format!("{:?}", cb), "[-4, -2, 20]");
+        assert_eq!(cb.clone(), cb);
+    }
+
+    #[test]
+    fn test_zero_capacity(){
+        let mut cb: ArrayCircularBuffer<i32, 0> = ArrayCircularBuffer::new();
+        cb.push_back(1);
+        cb.push_front(1);
+        assert!(cb.is_empty());
+        assert_eq!(cb.try_push_back(1), Err(1));
+        assert_eq!(cb.pop_back(), None);
+    }
+
+    #[test]
+    fn test_drop(){
+        let rc = Rc::new(());
+        {
+            let mut cb: ArrayCircularBuffer<Rc<()>, 4> = ArrayCircularBuffer::new();
+            for _ in 0..10 {
+                cb.push_back(rc.clone());
+            }
+            assert_eq!(Rc::strong_count(&rc), 5);
+            cb.pop_front();
+            assert_eq!(Rc::strong_count(&rc), 4);
+        }
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn test_storages(){
+        let rc = Rc::new(());
+        let mut memory: [MaybeUninit<Rc<()>>; 3] = [const { MaybeUninit::uninit() }; 3];
+        {
+            let mut cb = GenericCircularBuffer::from_storage(&mut memory[..]);
+            cb.extend((0..5).map(|_| rc.clone()));
+            assert_eq!(Rc::strong_count(&rc), 4);
+        }
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let boxed: Box<[MaybeUninit<i32>]> = (0..2).map(|_| MaybeUninit::uninit()).collect();
+        let mut heap = GenericCircularBuffer::from_storage(boxed);
+        heap.extend(1..4);
+        let array: ArrayCircularBuffer<i32, 2> = [2, 3].iter().cloned().collect();
+        assert_eq!(heap, array);
+        assert_eq!(heap.into_storage().len(), 2);
+
+        let mut empty = GenericCircularBuffer::from_storage(&mut [][..]);
+        empty.push_front(1);
+        assert_eq!(empty.pop_back(), None);
+    }
+
+    struct VecStorage(Vec<MaybeUninit<i32>>);
+
+    unsafe impl Storage<i32> for VecStorage {
+        fn slots(&self) -> &[MaybeUninit<i32>] {
+            &self.0
+        }
+
+        fn slots_mut(&mut self) -> &mut [MaybeUninit<i32>] {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn test_custom_storage(){
+        let storage = VecStorage((0..3).map(|_| MaybeUninit::uninit()).collect());
+        let mut cb = GenericCircularBuffer::from_storage(storage);
+        assert_eq!(cb.capacity(), 3);
+        cb.extend(1..5);
+        assert_eq!(cb.slices(), (&[2, 3][..], &[4][..]));
+        assert!(cb.is_full());
+    }
+}
@@ -0,0 +1,143 @@
+use super::circular::{CircularBuffer, OnEvict};
+use super::eviction::EvictionPolicy;
+
+/**
+Configures and creates a `CircularBuffer`.
+
+All options are optional. If the capacity is not provided, the buffer is exactly
+as large as its initial contents. If there are more initial elements than the capacity,
+only the newest ones are kept, regardless of the eviction policy.
+Cloning the builder does not copy the eviction callback, the same as cloning a `CircularBuffer`.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::{CircularBufferBuilder, EvictionPolicy};
+
+fn main(){
+    let cb = CircularBufferBuilder::new()
+        .capacity(4)
+        .contents(vec![1, 2, 3])
+        .policy(EvictionPolicy::Reject)
+        .build();
+    assert_eq!(cb.capacity(), 4);
+    assert_eq!(cb, [1, 2, 3].as_ref());
+    assert_eq!(cb.policy(), EvictionPolicy::Reject);
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct CircularBufferBuilder<T> {
+    capacity: Option<usize>,
+    contents: Vec<T>,
+    policy: EvictionPolicy,
+    max_capacity: usize,
+    on_evict: OnEvict<T>
+}
+
+impl<T> CircularBufferBuilder<T> {
+    ///Creates a builder with default options.
+    pub fn new() -> Self {
+        Self {
+            capacity: None,
+            contents: Vec::new(),
+            policy: EvictionPolicy::default(),
+            max_capacity: 0,
+            on_evict: OnEvict::default()
+        }
+    }
+
+    ///Sets the capacity of the buffer.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    ///Adds elements that the buffer initially contains, from the front to back.
+    pub fn contents<I>(mut self, contents: I) -> Self where I: IntoIterator<Item=T> {
+        self.contents.extend(contents);
+        self
+    }
+
+    ///Sets the eviction policy of the buffer.
+    pub fn policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    ///Sets the capacity up to which the buffer grows when it is full, see `CircularBuffer::with_growth()`.
+    pub fn max_capacity(mut self, max_capacity: usize) -> Self {
+        self.max_capacity = max_capacity;
+        self
+    }
+
+    /**
+    Sets the function receiving elements removed by overwriting pushes, see `CircularBuffer::set_on_evict()`.
+
+    Initial contents that do not fit into the buffer are dropped without calling the function.
+    */
+    pub fn on_evict<F>(mut self, f: F) -> Self where F: FnMut(T) + Send + Sync + 'static {
+        self.on_evict = OnEvict(Some(Box::new(f)));
+        self
+    }
+
+    ///Creates the configured buffer.
+    pub fn build(self) -> CircularBuffer<T> {
+        let capacity = self.capacity.unwrap_or(self.contents.len());
+        let mut cb = CircularBuffer::new(capacity);
+        cb.extend(self.contents);
+        cb.set_policy(self.policy);
+        cb.set_max_capacity(self.max_capacity);
+        cb.replace_on_evict(self.on_evict);
+        cb
+    }
+}
+
+impl<T> Default for CircularBufferBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_build(){
+        let cb: CircularBuffer<i32> = CircularBufferBuilder::new().build();
+        assert_eq!(cb.capacity(), 0);
+        let cb = CircularBufferBuilder::new().contents(vec![1, 2]).contents(vec![3]).build();
+        assert_eq!(cb.capacity(), 3);
+        assert!(cb.is_full());
+        let cb = CircularBufferBuilder::new()
+            .policy(EvictionPolicy::Panic)
+            .capacity(2)
+            .contents(1..6)
+            .build();
+        assert_eq!(cb, [4, 5].as_ref());
+        assert_eq!(cb.policy(), EvictionPolicy::Panic);
+        let mut cb = CircularBufferBuilder::new().capacity(1).contents(1..3).max_capacity(4).build();
+        assert_eq!(cb, [2].as_ref());
+        cb.extend(3..6);
+        assert_eq!(cb, [2, 3, 4, 5].as_ref());
+        assert_eq!(cb.capacity(), 4);
+    }
+
+    #[test]
+    fn test_on_evict(){
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        let builder = CircularBufferBuilder::new()
+            .capacity(2)
+            .contents(1..4)
+            .on_evict(move |x| sink.lock().unwrap().push(x));
+        assert!(format!("{:?}", builder.clone()).contains("on_evict: None"));
+        let mut cb = builder.build();
+        assert_eq!(cb, [2, 3].as_ref());
+        assert!(evicted.lock().unwrap().is_empty());
+        cb.extend(4..6);
+        assert_eq!(*evicted.lock().unwrap(), vec![2, 3]);
+    }
+}
@@ -0,0 +1,145 @@
+use std::io::{BufRead, IoSlice};
+use bytes::{Buf, BufMut};
+use bytes::buf::UninitSlice;
+use super::circular::CircularBuffer;
+
+/*
+`CircularBuffer<u8>` works as a bounded byte FIFO for codecs based on the bytes crate.
+Both traits expose the internal memory directly: `chunk()` returns the front part
+of the content and `chunk_mut()` the free space after the back, so no bytes are copied
+into temporary buffers. Writing never overwrites unread data and does not grow the buffer.
+*/
+
+/**
+Reads bytes from the front of the buffer.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use bytes::Buf;
+
+fn main(){
+    let mut cb: CircularBuffer<u8> = CircularBuffer::new(4);
+    cb.extend(b"\x00\x01\x02\x03\x04\x05".iter().cloned());
+    //content crosses the end of the internal buffer
+    assert_eq!(cb.remaining(), 4);
+    assert_eq!(cb.get_u16(), 0x0203);
+    assert_eq!(cb.get_u8(), 4);
+    assert_eq!(cb.chunk(), b"\x05");
+}
+```
+*/
+impl Buf for CircularBuffer<u8> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.slices().0
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let (a, b) = self.slices();
+        let mut n = 0;
+        for slice in [a, b].iter().filter(|s| !s.is_empty()) {
+            if n == dst.len() {
+                break;
+            }
+            dst[n] = IoSlice::new(slice);
+            n += 1;
+        }
+        n
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        if cnt > self.len() {
+            panic!("Cannot advance by {} bytes, only {} remaining.", cnt, self.len());
+        }
+        self.consume(cnt);
+    }
+}
+
+/**
+Writes bytes into the free space at the back of the buffer.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use bytes::BufMut;
+
+fn main(){
+    let mut cb: CircularBuffer<u8> = CircularBuffer::new(6);
+    cb.put_u32(0x01020304);
+    assert_eq!(cb.remaining_mut(), 2);
+    cb.put_slice(b"ab");
+    assert_eq!(cb, b"\x01\x02\x03\x04ab".as_ref());
+}
+```
+*/
+unsafe impl BufMut for CircularBuffer<u8> {
+    fn remaining_mut(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let available = self.spare_capacity_mut().0.len();
+        if cnt > available {
+            panic!("Cannot advance by {} bytes, only {} available.", cnt, available);
+        }
+        self.commit(cnt);
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        self.spare_capacity_mut().0.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buf(){
+        let mut cb: CircularBuffer<u8> = CircularBuffer::new(5);
+        cb.extend(0..8);
+        assert_eq!(cb.chunk(), &[3, 4]);
+        let mut slices = [IoSlice::new(&[]); 3];
+        assert_eq!(cb.chunks_vectored(&mut slices), 2);
+        assert_eq!((&*slices[0], &*slices[1]), (&[3, 4][..], &[5, 6, 7][..]));
+        assert_eq!(cb.chunks_vectored(&mut slices[..1]), 1);
+        let mut out = [0u8; 4];
+        cb.copy_to_slice(&mut out);
+        assert_eq!(out, [3, 4, 5, 6]);
+        assert_eq!(cb.remaining(), 1);
+        cb.advance(1);
+        assert_eq!(cb.chunks_vectored(&mut [IoSlice::new(&[])]), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_too_far(){
+        let mut cb: CircularBuffer<u8> = CircularBuffer::new(5);
+        cb.extend(0..2);
+        cb.advance(3);
+    }
+
+    #[test]
+    fn test_buf_mut(){
+        let mut cb: CircularBuffer<u8> = CircularBuffer::new(5);
+        cb.put_slice(b"abc");
+        cb.advance(2);
+        //free space wraps around the end of the internal buffer
+        assert_eq!(cb.remaining_mut(), 4);
+        cb.put_slice(b"defg");
+        assert_eq!(cb.remaining_mut(), 0);
+        assert_eq!(cb.chunk_mut().len(), 0);
+        assert_eq!(cb, b"cdefg".as_ref());
+        let mut lazy: CircularBuffer<u8> = CircularBuffer::default();
+        lazy.set_capacity(3);
+        lazy.put_u8(7);
+        assert_eq!(lazy, [7].as_ref());
+        assert_eq!(CircularBuffer::<u8>::new(0).chunk_mut().len(), 0);
+    }
+}
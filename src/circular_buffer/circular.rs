@@ -1,15 +1,26 @@
-use std::mem::{ManuallyDrop, uninitialized, swap, drop, transmute};
-use std::ops::{Index, IndexMut};
-use std::iter::{Extend, FromIterator, IntoIterator};
+use std::mem::{replace, MaybeUninit};
+use std::ops::{Index, IndexMut, RangeBounds};
+use std::iter::{Extend, FromIterator, IntoIterator, Rev};
 use std::cmp::{Ord, PartialEq, Eq, PartialOrd, Ordering};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::collections::{TryReserveError, VecDeque};
+use std::ptr;
 
-use super::iter::{Iter, IterMut, Drain, IntoIter};
+use super::array::GenericCircularBuffer;
+use super::eviction::{EvictionPolicy, ExtendReport, ResizePolicy};
+use crate::interval::IntervalSet;
+use super::iter::{Iter, IterMut, Drain, IntoIter, Chunks, ChunksExact, Windows, PaddedView};
+use super::slice::{CircularSlice, Layout};
 
 
 /**
 Circular buffer implementation.
 
+Elements are stored in a `GenericCircularBuffer` backed by a boxed slice. On top of it
+`CircularBuffer` manages the memory - it can be resized, grow when it is full or allocate
+lazily - and adds eviction policies, sequence numbers and eviction callbacks.
+
 # Example
 
 ```
@@ -46,11 +57,46 @@ fn main(){
 }
 ```
 */
-#[derive(Clone)]
 pub struct CircularBuffer<T> {
-    buffer: Box<[ManuallyDrop<T>]>,
-    start: usize,
-    end:usize
+    ring: GenericCircularBuffer<T, Box<[MaybeUninit<T>]>>,
+    //sequence number of the first element
+    seq: u64,
+    policy: EvictionPolicy,
+    //capacity up to which a full buffer grows instead of evicting elements
+    max_capacity: usize,
+    on_evict: OnEvict<T>,
+    //capacity allocated by the first push if the buffer has no memory yet
+    lazy_capacity: Option<usize>,
+    totals: Totals
+}
+
+//numbers of elements pushed, evicted and popped since the last reset
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    pushed: u64,
+    evicted: u64,
+    popped: u64
+}
+
+//a callback receiving elements removed by overwriting pushes, not copied by clone()
+pub(super) struct OnEvict<T>(pub(super) Option<Box<dyn FnMut(T) + Send + Sync>>);
+
+impl<T> Default for OnEvict<T> {
+    fn default() -> Self {
+        OnEvict(None)
+    }
+}
+
+impl<T> Clone for OnEvict<T> {
+    fn clone(&self) -> Self {
+        OnEvict::default()
+    }
+}
+
+impl<T> fmt::Debug for OnEvict<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Some(..)" } else { "None" })
+    }
 }
 
 impl<T> CircularBuffer<T> {
@@ -87,66 +133,75 @@ impl<T> CircularBuffer<T> {
     ```
     */
     pub fn with_capacity(capacity: usize) -> Self {
-
-        let mut buffer = Vec::with_capacity(capacity+1);
-        for _ in 0..capacity+1 {
-            buffer.push(ManuallyDrop::new(unsafe{uninitialized()}));
-        }
-        Self {
-            buffer: buffer.into_boxed_slice(),
-            start: 0,
-            end: 0
-        }
+        Self::from_ring(GenericCircularBuffer::from_storage(Self::slots(capacity)))
     }
 
     /**
-    Returns current number of elements in the buffer.
+    Creates a new instance of `CircularBuffer` with the given capacity and eviction policy.
 
     # Example
 
     ```
-    use advanced_collections::circular_buffer::CircularBuffer;
+    use advanced_collections::circular_buffer::{CircularBuffer, EvictionPolicy};
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
-        assert_eq!(cb.len(), 0);
+        let mut cb = CircularBuffer::with_policy(1, EvictionPolicy::Reject);
         cb.push_back(1);
-        assert_eq!(cb.len(), 1);
-
+        cb.push_back(2);
+        assert_eq!(cb, [1].as_ref());
     }
     ```
     */
-    pub fn len(&self) -> usize {
-        if self.start <= self.end {
-            self.end - self.start
-        } else {
-            self.buffer.len() + self.end - self.start
-        }
+    pub fn with_policy(capacity: usize, policy: EvictionPolicy) -> Self {
+        let mut cb = Self::with_capacity(capacity);
+        cb.policy = policy;
+        cb
     }
 
     /**
-    Returns maximal number of elements that can be stored in the buffer.
+    Registers a function called with every element removed by an overwriting push.
+
+    Evicted elements are passed to the function instead of being dropped, so they can be
+    stored elsewhere or aggregated. Elements removed by other operations, like `pop_front()`,
+    `clear()` or `resize()`, are not passed to it. Clones of the buffer do not share the function.
 
     # Example
 
     ```
     use advanced_collections::circular_buffer::CircularBuffer;
+    use std::sync::{Arc, Mutex};
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
-        assert_eq!(cb.capacity(), 5);
-        cb.resize(7);
-        assert_eq!(cb.capacity(), 7);
-
+        let flushed = Arc::new(Mutex::new(Vec::new()));
+        let sink = flushed.clone();
+        let mut cb = CircularBuffer::new(2);
+        cb.set_on_evict(move |x| sink.lock().unwrap().push(x));
+        cb.extend(1..6);
+        assert_eq!(cb, [4,5].as_ref());
+        assert_eq!(*flushed.lock().unwrap(), vec![1,2,3]);
     }
     ```
     */
-    pub fn capacity(&self) -> usize {
-        self.buffer.len() - 1
+    pub fn set_on_evict<F>(&mut self, f: F) where F: FnMut(T) + Send + Sync + 'static {
+        self.on_evict = OnEvict(Some(Box::new(f)));
+    }
+
+    //sets a callback that was already boxed, e.g. by the builder
+    pub(super) fn replace_on_evict(&mut self, on_evict: OnEvict<T>) {
+        self.on_evict = on_evict;
+    }
+
+    ///Removes the function registered by `set_on_evict()`, evicted elements are dropped again.
+    pub fn remove_on_evict(&mut self) {
+        self.on_evict = OnEvict::default();
     }
 
     /**
-    Changes internal size of the buffer.
+    Creates a new instance of `CircularBuffer` that grows instead of evicting elements.
+
+    When the buffer is full, its capacity is doubled until it reaches `max_capacity`.
+    Only then the eviction policy is applied. This makes the buffer a hybrid between
+    `VecDeque` and a bounded circular buffer.
 
     # Example
 
@@ -154,35 +209,45 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        let mut cb = CircularBuffer::with_growth(2, 5);
+        cb.extend(1..4);
+        assert_eq!(cb.capacity(), 4);
+        cb.extend(4..8);
         assert_eq!(cb.capacity(), 5);
-        cb.resize(7);
-        assert_eq!(cb.capacity(), 7);
-
+        assert_eq!(cb, [3,4,5,6,7].as_ref());
     }
     ```
     */
-    pub fn resize (&mut self, capacity: usize) {
-        let mut new_buf = Vec::with_capacity(capacity+1);
-        let to_be_skipped = if self.len()>capacity{
-            self.len() - capacity
-        } else {
-            0
-        };
-        new_buf.extend(self.drain().skip(to_be_skipped).map(|x| ManuallyDrop::new(x)));
-        let elem_num = new_buf.len();
-        for _ in 0..capacity -new_buf.len() + 1{
-            new_buf.push(ManuallyDrop::new(unsafe{uninitialized()}));
-        }
-        new_buf.shrink_to_fit();
-        self.buffer = new_buf.into_boxed_slice();
-        self.start = 0;
-        self.end = elem_num;
+    pub fn with_growth(initial: usize, max_capacity: usize) -> Self {
+        let mut cb = Self::with_capacity(initial);
+        cb.max_capacity = max_capacity;
+        cb
     }
 
+    /**
+    Returns the capacity up to which the buffer grows when it is full.
+
+    Buffers that do not grow return a value not greater than their capacity.
+    */
+    pub fn max_capacity(&self) -> usize {
+        self.max_capacity
+    }
 
     /**
-    Checks if the buffer is empty.
+    Sets the capacity up to which the buffer grows when it is full.
+
+    A value not greater than the current capacity disables growing.
+    */
+    pub fn set_max_capacity(&mut self, max_capacity: usize) {
+        self.max_capacity = max_capacity;
+    }
+
+    /**
+    Describes the internal state of the buffer: position of the front in the internal memory,
+    the number of elements and the capacity.
+
+    The `Debug` implementation prints elements of the buffer, this function is meant for
+    diagnosing the buffer itself and does not require elements to implement `Debug`.
 
     # Example
 
@@ -190,81 +255,57 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
-        assert!(cb.is_empty());
-        cb.push_back(1);
-        assert!(!cb.is_empty());
-
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(1..5);
+        assert_eq!(format!("{:?}", cb), "[2, 3, 4]");
+        assert_eq!(cb.internal_state(), "CircularBuffer{ start: 1, len: 3, capacity: 3 }");
     }
     ```
     */
-    pub fn is_empty(&self) -> bool {
-        self.end == self.start
+    pub fn internal_state(&self) -> String {
+        format!("CircularBuffer{{ start: {}, len: {}, capacity: {} }}", self.ring.start(), self.len(), self.capacity())
     }
 
     /**
-    Checks if the buffer is full.
+    Returns the eviction policy of the buffer.
 
     # Example
 
     ```
-    use advanced_collections::circular_buffer::CircularBuffer;
+    use advanced_collections::circular_buffer::{CircularBuffer, EvictionPolicy};
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(2);
-        assert!(!cb.is_full());
-        cb.push_back(1);
-        cb.push_back(2);
-        assert!(cb.is_full());
-
+        let cb: CircularBuffer<i32> = CircularBuffer::new(1);
+        assert_eq!(cb.policy(), EvictionPolicy::Overwrite);
     }
     ```
     */
-    pub fn is_full(&self) -> bool {
-        self.len() == self.capacity()
+    pub fn policy(&self) -> EvictionPolicy {
+        self.policy
     }
 
-
     /**
-    Places elements at the end of the buffer.
-
-    If the buffer is full, it replaces elements from the front of the buffer.
+    Changes the eviction policy of the buffer.
 
     # Example
 
     ```
-    use advanced_collections::circular_buffer::CircularBuffer;
+    use advanced_collections::circular_buffer::{CircularBuffer, EvictionPolicy};
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(3);
-
-        cb.push_back(1);
-        cb.push_back(2);
-        cb.push_back(3);
-        assert_eq!(cb, [1,2,3].as_ref());
-        cb.push_back(4);
-        assert_eq!(cb, [2,3,4].as_ref());
+        let mut cb = CircularBuffer::from(vec![1, 2]);
+        cb.set_policy(EvictionPolicy::Reject);
+        cb.push_front(0);
+        assert_eq!(cb, [1, 2].as_ref());
     }
     ```
     */
-    pub fn push_back(&mut self, val: T) {
-        if self.is_full(){
-            if self.capacity() == 0 {
-                return;
-            } else {
-                self.pop_front();
-            }
-        }
-        self.push_at(val, self.end);
-        self.incr_end();
+    pub fn set_policy(&mut self, policy: EvictionPolicy) {
+        self.policy = policy;
     }
 
-
-
     /**
-    Places elements at the beginning of the buffer.
-
-    If the buffer is full, it replaces elements from the back of the buffer.
+    Creates a full buffer with the given capacity, filled with copies of the value.
 
     # Example
 
@@ -272,31 +313,20 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb:CircularBuffer<i32> = CircularBuffer::new(3);
-
-        cb.push_front(1);
-        cb.push_front(2);
-        cb.push_front(3);
-        assert_eq!(cb, [3,2,1].as_ref());
-        cb.push_front(4);
-        assert_eq!(cb, [4,3,2].as_ref());
+        let mut cb = CircularBuffer::filled(3, 0.0);
+        assert!(cb.is_full());
+        cb.push_back(1.0);
+        assert_eq!(cb, [0.0, 0.0, 1.0].as_ref());
     }
     ```
     */
-    pub fn push_front(&mut self, val: T) {
-        if self.is_full(){
-            if self.capacity() == 0 {
-                return;
-            } else {
-                self.pop_back();
-            }
-        }
-        self.decr_start();
-        self.push_at(val, self.start);
+    pub fn filled(capacity: usize, val: T) -> Self where T: Clone {
+        Self::from(vec![val; capacity])
     }
 
     /**
-    Pops an element from the end of the buffer.
+    Creates a full buffer with the given capacity,
+    with each element created by calling the closure with its index.
 
     # Example
 
@@ -304,25 +334,17 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb = CircularBuffer::from(vec![1,2]);
-
-        assert_eq!(cb.pop_back(), Some(2));
-        assert_eq!(cb.pop_back(), Some(1));
-        assert_eq!(cb.pop_back(), None);
+        let cb = CircularBuffer::from_fn(4, |i| i * i);
+        assert_eq!(cb, [0, 1, 4, 9].as_ref());
     }
     ```
     */
-    pub fn pop_back(&mut self) -> Option<T> {
-        if self.is_empty(){
-            None
-        } else {
-            self.decr_end();
-            Some(self.pop_at(self.end))
-        }
+    pub fn from_fn<F>(capacity: usize, f: F) -> Self where F: FnMut(usize) -> T {
+        Self::from((0..capacity).map(f).collect::<Vec<T>>())
     }
 
     /**
-    Pops an element from the beginning of the buffer.
+    Returns current number of elements in the buffer.
 
     # Example
 
@@ -330,26 +352,20 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb = CircularBuffer::from(vec![1,2]);
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        assert_eq!(cb.len(), 0);
+        cb.push_back(1);
+        assert_eq!(cb.len(), 1);
 
-        assert_eq!(cb.pop_front(), Some(1));
-        assert_eq!(cb.pop_front(), Some(2));
-        assert_eq!(cb.pop_front(), None);
     }
     ```
     */
-    pub fn pop_front(&mut self) -> Option<T> {
-        if self.is_empty(){
-            None
-        } else {
-            let tmp = self.pop_at(self.start);
-            self.incr_start();
-            Some(tmp)
-        }
+    pub fn len(&self) -> usize {
+        self.ring.len()
     }
 
     /**
-    Clears content of the buffer.
+    Returns maximal number of elements that can be stored in the buffer.
 
     # Example
 
@@ -357,42 +373,46 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb = CircularBuffer::from(vec![1,2]);
-        cb.clear();
-        assert!(cb.is_empty());
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        assert_eq!(cb.capacity(), 5);
+        cb.resize(7);
+        assert_eq!(cb.capacity(), 7);
+
     }
     ```
     */
-    pub fn clear(&mut self) {
-        while let Some(val) = self.pop_back() {
-            drop(val)
-        }
+    pub fn capacity(&self) -> usize {
+        self.lazy_capacity.unwrap_or_else(|| self.ring.capacity())
     }
 
     /**
-    Returns an iterator over the buffer from the front to back.
+    Changes internal size of the buffer.
 
     # Example
 
     ```
     use advanced_collections::circular_buffer::CircularBuffer;
-    use std::iter::FromIterator;
 
     fn main(){
-        let mut cb = CircularBuffer::from(vec![1,2,3]);
-        let v = Vec::from_iter(cb.iter());
-        assert_eq!(v, vec![&1,&2,&3]);
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        assert_eq!(cb.capacity(), 5);
+        cb.resize(7);
+        assert_eq!(cb.capacity(), 7);
+
     }
     ```
     */
-    pub fn iter(&self) -> Iter<T> {
-
-        let (a,b) = self.slices();
-        a.iter().chain(b.iter())
+    pub fn resize (&mut self, capacity: usize) {
+        self.resize_into(Self::slots(capacity));
     }
 
     /**
-    Returns a mutable iterator over the buffer from the front to back.
+    Changes the capacity of the buffer, deferring the allocation if possible.
+
+    If the buffer has not allocated memory yet, e.g. it was created by `default()`,
+    memory is allocated when the first element is pushed. Otherwise this function
+    works like `resize()`. This lets buffers live in rarely used struct fields
+    without paying for the allocation.
 
     # Example
 
@@ -400,24 +420,29 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-        let mut cb = CircularBuffer::from(vec![1,2,3]);
-        for  a in cb.iter_mut(){
-            *a+= 1;
-        }
+        let mut cb: CircularBuffer<i32> = CircularBuffer::default();
+        cb.set_capacity(3);
+        assert_eq!(cb.capacity(), 3);
+        //memory is allocated here
+        cb.extend(1..5);
         assert_eq!(cb, [2,3,4].as_ref());
     }
     ```
     */
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        let (a,b) = self.slices_mut();
-        a.iter_mut().chain(b.iter_mut())
+    pub fn set_capacity(&mut self, capacity: usize) {
+        if self.lazy_capacity.is_some() {
+            self.lazy_capacity = Some(capacity);
+        } else {
+            self.resize(capacity);
+        }
     }
 
     /**
-    Appends content of one CircularBuffer at the end of another.
+    Reduces the capacity of the buffer to the number of stored elements.
 
-    If the buffer is too small for the content, elements from the begging of the buffer
-    get replaced by elements from the end of the buffer.
+    Elements are moved into a new, smaller allocation and become contiguous.
+    This gives memory back after the buffer was temporarily resized to a large capacity.
+    A buffer that has not allocated memory yet only changes its capacity.
 
     # Example
 
@@ -425,43 +450,56 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-       let mut c1 = CircularBuffer::from(vec![1,2,3]);
-       let mut c2 = CircularBuffer::from(vec![4,5,6,7]);
-       c1.append(&mut c2);
-       assert_eq!(c1, [5,6,7].as_ref());
-       assert!(c2.is_empty());
+        let mut cb = CircularBuffer::new(1000);
+        cb.extend(1..4);
+        cb.shrink_to_fit();
+        assert_eq!(cb.capacity(), 3);
+        assert_eq!(cb, [1,2,3].as_ref());
     }
     ```
     */
-    pub fn append(&mut self, other: &mut Self) {
-        self.extend(other.drain())
+    pub fn shrink_to_fit(&mut self) {
+        let len = self.len();
+        if self.lazy_capacity.is_some() {
+            self.lazy_capacity = Some(len);
+        } else if len < self.capacity() {
+            self.resize(len);
+        }
     }
 
     /**
-    Returns a draining iterator over the buffer.
+    Changes internal size of the buffer, choosing which elements are kept when it shrinks.
+
+    `resize()` is equivalent to this function with `ResizePolicy::KeepNewest`.
 
     # Example
 
     ```
-    use advanced_collections::circular_buffer::CircularBuffer;
-    use std::iter::FromIterator;
+    use advanced_collections::circular_buffer::{CircularBuffer, ResizePolicy};
 
     fn main(){
-       let mut cb = CircularBuffer::from(vec![1,2,3]);
-       let v = Vec::from_iter(cb.drain());
-       assert_eq!(v, vec![1,2,3]);
-       assert!(cb.is_empty());
+        let mut cb = CircularBuffer::from(vec![1,2,3,4,5]);
+        cb.resize_with_policy(3, ResizePolicy::KeepOldest);
+        assert_eq!(cb.capacity(), 3);
+        assert_eq!(cb, [1,2,3].as_ref());
     }
     ```
     */
-    pub fn drain(&mut self) -> Drain<T>{
-        Drain::new(self)
+    pub fn resize_with_policy(&mut self, capacity: usize, policy: ResizePolicy) {
+        if policy == ResizePolicy::KeepOldest {
+            while self.len() > capacity {
+                self.take_back();
+            }
+        }
+        self.resize(capacity);
     }
 
     /**
-    Returns a reference to the first element of the buffer.
+    Clears the buffer and changes its capacity, reusing the allocation when possible.
 
-    Returns `None` if the buffer is empty.
+    The buffer becomes equivalent to a new one with the given capacity and the current eviction policy,
+    sequence numbers start from 0 again. Memory is not allocated if the capacity does not change
+    and shrinking happens in place when the allocator supports it.
 
     # Example
 
@@ -469,23 +507,35 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-       let mut cb = CircularBuffer::from(vec![1,2,3]);
-       assert_eq!(cb.first(), Some(&1));
+        let mut window = CircularBuffer::new(3);
+        window.extend(vec![1, 2, 3, 4]);
+        window.recycle(2);
+        assert!(window.is_empty());
+        assert_eq!(window.capacity(), 2);
+        assert_eq!(window.next_seq(), 0);
     }
     ```
     */
-    pub fn first(&self) -> Option<&T> {
-        if self.is_empty(){
-            None
-        } else {
-            Some(&* self.buffer[self.internal_index(0)])
+    pub fn recycle(&mut self, capacity: usize) {
+        self.clear();
+        self.seq = 0;
+        self.totals = Totals::default();
+        if self.lazy_capacity.is_some() {
+            self.lazy_capacity = Some(capacity);
+        } else if capacity < self.capacity() {
+            //the buffer is empty, truncating uninitialized slots does not drop anything
+            let mut slots = self.take_ring().into_storage().into_vec();
+            slots.truncate(capacity);
+            self.ring = GenericCircularBuffer::from_storage(slots.into_boxed_slice());
+        } else if capacity > self.capacity() {
+            self.resize(capacity);
         }
     }
 
     /**
-    Returns a mutable reference to the first element of the buffer.
+    Changes internal size of the buffer, returning an error if the allocation fails.
 
-    Returns `None` if the buffer is empty.
+    The buffer is not modified if the allocation fails.
 
     # Example
 
@@ -493,24 +543,26 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-       let mut cb = CircularBuffer::from(vec![1,2,3]);
-       *cb.first_mut().unwrap() +=1;
-       assert_eq!(cb, [2,2,3].as_ref());
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        assert!(cb.try_resize(7).is_ok());
+        assert_eq!(cb.capacity(), 7);
+        assert!(cb.try_resize(usize::MAX).is_err());
+        assert_eq!(cb.capacity(), 7);
     }
     ```
     */
-    pub fn first_mut(&mut self) -> Option<&mut T> {
-        if self.is_empty(){
-            None
-        } else {
-            Some(&mut *self.buffer[self.internal_index(0)])
-        }
+    pub fn try_resize(&mut self, capacity: usize) -> Result<(), TryReserveError> {
+        let mut slots = Vec::new();
+        slots.try_reserve_exact(capacity)?;
+        //MaybeUninit slots do not need to be initialized
+        unsafe { slots.set_len(capacity) };
+        self.resize_into(slots.into_boxed_slice());
+        Ok(())
     }
 
     /**
-    Returns a reference to the last element of the buffer.
-
-    Returns `None` if the buffer is empty.
+    Creates a new instance of `CircularBuffer` with the given capacity,
+    returning an error if the allocation fails.
 
     # Example
 
@@ -518,23 +570,34 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      assert_eq!(cb.last(), Some(&3));
+        let cb:CircularBuffer<i32> = CircularBuffer::try_with_capacity(5).unwrap();
+        assert_eq!(cb.capacity(), 5);
+        assert!(CircularBuffer::<i32>::try_with_capacity(usize::MAX).is_err());
     }
     ```
     */
-    pub fn last(&self) -> Option<&T> {
-        if self.is_empty(){
-            None
-        } else {
-            Some(&* self.buffer[self.internal_index(self.len()-1)])
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut cb = Self::with_capacity(0);
+        cb.try_resize(capacity)?;
+        Ok(cb)
+    }
+
+    //moves content of the buffer into the new, already allocated storage
+    fn resize_into(&mut self, slots: Box<[MaybeUninit<T>]>) {
+        let mut ring = GenericCircularBuffer::from_storage(slots);
+        while self.len() > ring.capacity() {
+            self.take_front();
         }
+        while let Some(val) = self.ring.pop_front() {
+            ring.push_back(val);
+        }
+        self.ring = ring;
+        self.lazy_capacity = None;
     }
 
-    /**
-    Returns a mutable reference to the last element of the buffer.
 
-    Returns `None` if the buffer is empty.
+    /**
+    Checks if the buffer is empty.
 
     # Example
 
@@ -542,24 +605,794 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      *cb.last_mut().unwrap() +=1;
-      assert_eq!(cb, [1,2,4].as_ref());
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(5);
+        assert!(cb.is_empty());
+        cb.push_back(1);
+        assert!(!cb.is_empty());
+
     }
     ```
     */
-    pub fn last_mut(&mut self) -> Option<&mut T> {
-        if self.is_empty(){
-            None
-        } else {
-            Some(&mut * self.buffer[self.internal_index(self.len()-1)])
-        }
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
     }
 
     /**
-    Returns two slices to the internal buffer.
+    Checks if the buffer is full.
 
-    Because the internal buffer is circular, normally it is not possible to represent it
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(2);
+        assert!(!cb.is_full());
+        cb.push_back(1);
+        cb.push_back(2);
+        assert!(cb.is_full());
+
+    }
+    ```
+    */
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+
+    /**
+    Places elements at the end of the buffer.
+
+    If the buffer is full, it replaces elements from the front of the buffer.
+    This behavior can be changed using `EvictionPolicy`.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(3);
+
+        cb.push_back(1);
+        cb.push_back(2);
+        cb.push_back(3);
+        assert_eq!(cb, [1,2,3].as_ref());
+        cb.push_back(4);
+        assert_eq!(cb, [2,3,4].as_ref());
+    }
+    ```
+    */
+    pub fn push_back(&mut self, val: T) {
+        self.allocate();
+        if self.is_full() && !self.grow(){
+            if !self.make_space() {
+                return;
+            }
+            let evicted = self.take_front();
+            self.evicted(evicted);
+        }
+        self.ring.push_back(val);
+        self.totals.pushed = self.totals.pushed.wrapping_add(1);
+    }
+
+
+
+    /**
+    Places elements at the beginning of the buffer.
+
+    If the buffer is full, it replaces elements from the back of the buffer.
+    This behavior can be changed using `EvictionPolicy`.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(3);
+
+        cb.push_front(1);
+        cb.push_front(2);
+        cb.push_front(3);
+        assert_eq!(cb, [3,2,1].as_ref());
+        cb.push_front(4);
+        assert_eq!(cb, [4,3,2].as_ref());
+    }
+    ```
+    */
+    pub fn push_front(&mut self, val: T) {
+        self.allocate();
+        if self.is_full() && !self.grow(){
+            if !self.make_space() {
+                return;
+            }
+            let evicted = self.take_back();
+            self.evicted(evicted);
+        }
+        self.seq = self.seq.wrapping_sub(1);
+        self.ring.push_front(val);
+        self.totals.pushed = self.totals.pushed.wrapping_add(1);
+    }
+
+    /**
+    Places an element at the end of the buffer if there is space for it.
+
+    Unlike `push_back()` it never removes existing elements.
+    If the buffer is full, the element is given back as an error.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(2);
+        assert_eq!(cb.try_push_back(1), Ok(()));
+        assert_eq!(cb.try_push_back(2), Ok(()));
+        assert_eq!(cb.try_push_back(3), Err(3));
+        assert_eq!(cb, [1,2].as_ref());
+    }
+    ```
+    */
+    pub fn try_push_back(&mut self, val: T) -> Result<(), T> {
+        if self.is_full() && !self.grow() {
+            return Err(val);
+        }
+        self.push_back(val);
+        Ok(())
+    }
+
+    /**
+    Places an element at the beginning of the buffer if there is space for it.
+
+    Unlike `push_front()` it never removes existing elements.
+    If the buffer is full, the element is given back as an error.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb:CircularBuffer<i32> = CircularBuffer::new(2);
+        assert_eq!(cb.try_push_front(1), Ok(()));
+        assert_eq!(cb.try_push_front(2), Ok(()));
+        assert_eq!(cb.try_push_front(3), Err(3));
+        assert_eq!(cb, [2,1].as_ref());
+    }
+    ```
+    */
+    pub fn try_push_front(&mut self, val: T) -> Result<(), T> {
+        if self.is_full() && !self.grow() {
+            return Err(val);
+        }
+        self.push_front(val);
+        Ok(())
+    }
+
+    /**
+    Pops an element from the end of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2]);
+
+        assert_eq!(cb.pop_back(), Some(2));
+        assert_eq!(cb.pop_back(), Some(1));
+        assert_eq!(cb.pop_back(), None);
+    }
+    ```
+    */
+    pub fn pop_back(&mut self) -> Option<T> {
+        let val = self.take_back();
+        if val.is_some() {
+            self.totals.popped = self.totals.popped.wrapping_add(1);
+        }
+        val
+    }
+
+    /**
+    Pops an element from the beginning of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2]);
+
+        assert_eq!(cb.pop_front(), Some(1));
+        assert_eq!(cb.pop_front(), Some(2));
+        assert_eq!(cb.pop_front(), None);
+    }
+    ```
+    */
+    pub fn pop_front(&mut self) -> Option<T> {
+        let val = self.take_front();
+        if val.is_some() {
+            self.totals.popped = self.totals.popped.wrapping_add(1);
+        }
+        val
+    }
+
+    /**
+    Pops an element from the end of the buffer if the predicate returns `true` for it.
+
+    Returns `None` if the buffer is empty or the predicate returns `false`.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2,3,4]);
+
+        while let Some(_) = cb.pop_back_if(|x| *x > 2) {}
+        assert_eq!(cb, [1,2].as_ref());
+    }
+    ```
+    */
+    pub fn pop_back_if<F>(&mut self, pred: F) -> Option<T> where F: FnOnce(&mut T) -> bool {
+        if pred(self.last_mut()?) {
+            self.pop_back()
+        } else {
+            None
+        }
+    }
+
+    /**
+    Pops an element from the beginning of the buffer if the predicate returns `true` for it.
+
+    Returns `None` if the buffer is empty or the predicate returns `false`.
+    This is useful for draining a queue up to a boundary element.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        //timestamps of events
+        let mut cb = CircularBuffer::from(vec![10, 20, 30, 40]);
+        let cutoff = 25;
+
+        let mut expired = Vec::new();
+        while let Some(t) = cb.pop_front_if(|t| *t < cutoff) {
+            expired.push(t);
+        }
+        assert_eq!(expired, vec![10, 20]);
+        assert_eq!(cb, [30, 40].as_ref());
+    }
+    ```
+    */
+    pub fn pop_front_if<F>(&mut self, pred: F) -> Option<T> where F: FnOnce(&mut T) -> bool {
+        if pred(self.first_mut()?) {
+            self.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /**
+    Clears content of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2]);
+        cb.clear();
+        assert!(cb.is_empty());
+    }
+    ```
+    */
+    pub fn clear(&mut self) {
+        self.seq = self.seq.wrapping_add(self.len() as u64);
+        self.ring.clear();
+    }
+
+    /**
+    Returns an iterator over the buffer from the front to back.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2,3]);
+        let v = Vec::from_iter(cb.iter());
+        assert_eq!(v, vec![&1,&2,&3]);
+    }
+    ```
+    */
+    pub fn iter(&self) -> Iter<T> {
+        self.ring.iter()
+    }
+
+    /**
+    Returns a mutable iterator over the buffer from the front to back.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::from(vec![1,2,3]);
+        for  a in cb.iter_mut(){
+            *a+= 1;
+        }
+        assert_eq!(cb, [2,3,4].as_ref());
+    }
+    ```
+    */
+    pub fn iter_mut(&mut self) -> IterMut<T> {
+        self.ring.iter_mut()
+    }
+
+    /**
+    Appends content of one CircularBuffer at the end of another.
+
+    If the buffer is too small for the content, elements from the begging of the buffer
+    get replaced by elements from the end of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+       let mut c1 = CircularBuffer::from(vec![1,2,3]);
+       let mut c2 = CircularBuffer::from(vec![4,5,6,7]);
+       c1.append(&mut c2);
+       assert_eq!(c1, [5,6,7].as_ref());
+       assert!(c2.is_empty());
+    }
+    ```
+    */
+    pub fn append(&mut self, other: &mut Self) {
+        self.extend(other.drain())
+    }
+
+    /**
+    Appends elements of the iterator, reporting how many were pushed and how many were evicted.
+
+    Elements evicted by later elements of the same iterator are counted as well.
+    Elements dropped because of the `EvictionPolicy::Reject` policy are neither pushed nor evicted.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::{CircularBuffer, EvictionPolicy};
+
+    fn main(){
+       let mut cb = CircularBuffer::from(vec![1,2]);
+       let report = cb.extend_report(vec![3,4,5]);
+       assert_eq!((report.pushed, report.evicted), (3, 3));
+
+       cb.set_policy(EvictionPolicy::Reject);
+       let report = cb.extend_report(vec![6]);
+       assert_eq!((report.pushed, report.evicted), (0, 0));
+    }
+    ```
+    */
+    pub fn extend_report<I>(&mut self, iter: I) -> ExtendReport where I: IntoIterator<Item=T> {
+        let mut report = ExtendReport::default();
+        for val in iter {
+            if self.is_full() && !self.grow() {
+                if !self.make_space() {
+                    continue;
+                }
+                report.evicted += 1;
+            }
+            self.push_back(val);
+            report.pushed += 1;
+        }
+        report
+    }
+
+    /**
+    Returns a draining iterator over the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::iter::FromIterator;
+
+    fn main(){
+       let mut cb = CircularBuffer::from(vec![1,2,3]);
+       let v = Vec::from_iter(cb.drain());
+       assert_eq!(v, vec![1,2,3]);
+       assert!(cb.is_empty());
+    }
+    ```
+    */
+    pub fn drain(&mut self) -> Drain<T>{
+        Drain::new(self)
+    }
+
+    /**
+    Returns a draining iterator over the buffer that starts from the newest elements.
+
+    Elements that were not consumed by the iterator stay in the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+       let mut cb = CircularBuffer::from(vec![1,2,3,4]);
+       let newest: Vec<_> = cb.drain_back().take(2).collect();
+       assert_eq!(newest, vec![4,3]);
+       assert_eq!(cb, [1,2].as_ref());
+    }
+    ```
+    */
+    pub fn drain_back(&mut self) -> Rev<Drain<'_, T>>{
+        Drain::new(self).rev()
+    }
+
+    /**
+    Returns a reference to the first element of the buffer.
+
+    Returns `None` if the buffer is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+       let mut cb = CircularBuffer::from(vec![1,2,3]);
+       assert_eq!(cb.first(), Some(&1));
+    }
+    ```
+    */
+    pub fn first(&self) -> Option<&T> {
+        self.ring.first()
+    }
+
+    /**
+    Returns a mutable reference to the first element of the buffer.
+
+    Returns `None` if the buffer is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+       let mut cb = CircularBuffer::from(vec![1,2,3]);
+       *cb.first_mut().unwrap() +=1;
+       assert_eq!(cb, [2,2,3].as_ref());
+    }
+    ```
+    */
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.ring.first_mut()
+    }
+
+    /**
+    Returns a reference to the last element of the buffer.
+
+    Returns `None` if the buffer is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.last(), Some(&3));
+    }
+    ```
+    */
+    pub fn last(&self) -> Option<&T> {
+        self.ring.last()
+    }
+
+    /**
+    Returns a mutable reference to the last element of the buffer.
+
+    Returns `None` if the buffer is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      *cb.last_mut().unwrap() +=1;
+      assert_eq!(cb, [1,2,4].as_ref());
+    }
+    ```
+    */
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.ring.last_mut()
+    }
+
+    /**
+    Returns a reference to the element at the given index.
+
+    Returns `None` if the index is out of bounds, unlike indexing which panics.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.get(1), Some(&2));
+      assert_eq!(cb.get(3), None);
+    }
+    ```
+    */
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.ring.get(index)
+    }
+
+    /**
+    Returns a mutable reference to the element at the given index.
+
+    Returns `None` if the index is out of bounds, unlike indexing which panics.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      if let Some(x) = cb.get_mut(2) {
+          *x = 5;
+      }
+      assert!(cb.get_mut(3).is_none());
+      assert_eq!(cb, [1,2,5].as_ref());
+    }
+    ```
+    */
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.ring.get_mut(index)
+    }
+
+    /**
+    Returns a reference to the element that would be returned by `pop_front()`.
+
+    This is an alias of `first()` for queue-like usage.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.peek_front(), Some(&1));
+    }
+    ```
+    */
+    pub fn peek_front(&self) -> Option<&T> {
+        self.first()
+    }
+
+    /**
+    Returns a reference to the element that would be returned by `pop_back()`.
+
+    This is an alias of `last()` for queue-like usage.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.peek_back(), Some(&3));
+    }
+    ```
+    */
+    pub fn peek_back(&self) -> Option<&T> {
+        self.last()
+    }
+
+    /**
+    Returns a reference to the n-th element counting from the back of the buffer.
+
+    `from_back(0)` returns the newest (last) element.
+    Returns `None` if `n` is outside of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.from_back(0), Some(&3));
+      assert_eq!(cb.from_back(2), Some(&1));
+      assert_eq!(cb.from_back(3), None);
+    }
+    ```
+    */
+    pub fn from_back(&self, n: usize) -> Option<&T> {
+        if n >= self.len() {
+            None
+        } else {
+            self.get(self.len() - 1 - n)
+        }
+    }
+
+    /**
+    Returns a mutable reference to the n-th element counting from the back of the buffer.
+
+    `from_back_mut(0)` returns the newest (last) element.
+    Returns `None` if `n` is outside of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      *cb.from_back_mut(1).unwrap() += 10;
+      assert_eq!(cb, [1,12,3].as_ref());
+    }
+    ```
+    */
+    pub fn from_back_mut(&mut self, n: usize) -> Option<&mut T> {
+        if n >= self.len() {
+            None
+        } else {
+            let index = self.len() - 1 - n;
+            self.get_mut(index)
+        }
+    }
+
+    /**
+    Returns two slices to the internal buffer.
+
+    Because the internal buffer is circular, normally it is not possible to represent it
+    as a single slice of data, but it is possible to represent it as two slices -
+    one after another.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.push_back(4);
+      cb.push_back(5);
+      assert_eq!(cb.slices(), ([3].as_ref(), [4,5].as_ref()));
+    }
+    ```
+    */
+    pub fn slices(&self) -> (&[T], &[T]){
+        self.ring.slices()
+    }
+
+    /**
+    Returns a read-only view of a range of elements, e.g. `cb.range(2..5)`.
+
+    Unlike `linearize()` it does not modify the buffer. The view handles ranges
+    crossing the end of the internal buffer.
+    Panics if the range is out of bounds.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      assert_eq!(cb.slices(), ([3,4].as_ref(), [5,6].as_ref()));
+      let view = cb.range(1..);
+      assert_eq!(view, [4,5,6].as_ref());
+      assert_eq!(cb.range(2..).iter().max(), Some(&6));
+    }
+    ```
+    */
+    pub fn range<R>(&self, range: R) -> CircularSlice<'_, T> where R: RangeBounds<usize> {
+        let (a, b) = self.slices();
+        CircularSlice::new(a, b, range)
+    }
+
+    /**
+    Returns an iterator over the `n` most recently pushed elements, the newest one last.
+
+    All elements are returned if the buffer contains fewer than `n` of them.
+    Use `rev()` to get the newest element first.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(1..9);
+        assert_eq!(cb.latest(3).cloned().collect::<Vec<_>>(), vec![6, 7, 8]);
+        assert_eq!(cb.latest(2).rev().cloned().collect::<Vec<_>>(), vec![8, 7]);
+        assert_eq!(cb.latest(10).len(), 5);
+    }
+    ```
+    */
+    pub fn latest(&self, n: usize) -> Iter<'_, T> {
+        self.range(self.len().saturating_sub(n)..).iter()
+    }
+
+    /**
+    Returns logical indexes of elements stored in the slices returned by `slices()`.
+
+    It allows mapping positions found in the slices, e.g. by a parser, back to indexes of the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let layout = cb.layout();
+      assert_eq!((layout.first, layout.second), (0..2, 2..4));
+    }
+    ```
+    */
+    pub fn layout(&self) -> Layout {
+        let (a, b) = self.slices();
+        Layout::new(a.len(), b.len())
+    }
+
+    /**
+    Maps a logical index to the number of the slice (0 or 1) returned by `slices()`
+    and the offset in that slice.
+
+    Returns `None` if the index is out of bounds.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let (slice, offset) = cb.logical_to_physical(3).unwrap();
+      assert_eq!(slice, 1);
+      assert_eq!(cb.slices().1[offset], cb[3]);
+      assert_eq!(cb.logical_to_physical(4), None);
+    }
+    ```
+    */
+    pub fn logical_to_physical(&self, index: usize) -> Option<(usize, usize)> {
+        self.layout().logical_to_physical(index)
+    }
+
+    /**
+    Returns two mutable slices to the internal buffer.
+
+    Because the internal buffer is circular, normally it is not possible to represent it
     as a single slice of data, but it is possible to represent it as two slices -
     one after another.
 
@@ -569,30 +1402,646 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      cb.push_back(4);
-      cb.push_back(5);
-      assert_eq!(cb.slices(), ([3,4].as_ref(), [5].as_ref()));
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.push_back(4);
+      cb.push_back(5);
+      let (mut a, mut b) = cb.slices_mut();
+      a[0] = 4;
+      b[0] = 5;
+      b[1] = 6;
+      assert_eq!(cb, [4,5,6].as_ref());
+    }
+    ```
+    */
+    pub fn slices_mut(&mut self) -> (&mut[T], &mut [T]) {
+        self.ring.slices_mut()
+    }
+
+    /**
+    Calls the closure on each continuous region of the buffer, from the front to back.
+
+    The closure is called once if the content of the buffer is continuous in memory
+    and twice if it wraps around the end of the internal buffer.
+    It is not called for an empty buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.push_back(4);
+      cb.push_back(5);
+      let mut calls = 0;
+      cb.for_each_slice_mut(|s| {
+          calls += 1;
+          for x in s.iter_mut() {
+              *x *= 10;
+          }
+      });
+      assert_eq!(calls, 2);
+      assert_eq!(cb, [30,40,50].as_ref());
+    }
+    ```
+    */
+    pub fn for_each_slice_mut<F>(&mut self, mut f: F) where F: FnMut(&mut [T]) {
+        let (a, b) = self.slices_mut();
+        if !a.is_empty() {
+            f(a);
+        }
+        if !b.is_empty() {
+            f(b);
+        }
+    }
+
+    /**
+    Rearranges content of the buffer to achieve a continuous region.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.push_back(4);
+      cb.push_back(5);
+      //slices() would now return [3], [4,5]
+      assert_eq!(cb.linearize(), [3,4,5].as_ref());
+    }
+    ```
+    */
+    pub fn linearize(&mut self) -> &mut [T]{
+        self.ring.linearize()
+    }
+
+
+
+    /**
+    Checks if all elements of the buffer are stored in one continuous region of memory.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      assert!(cb.is_contiguous());
+      cb.push_back(4);
+      cb.push_back(5);
+      assert!(!cb.is_contiguous());
+    }
+    ```
+    */
+    pub fn is_contiguous(&self) -> bool {
+        self.slices().1.is_empty()
+    }
+
+    /**
+    Returns all elements as one slice if they are stored in one continuous region of memory.
+
+    The buffer is not modified, `None` is returned if the content wraps around
+    the end of the internal buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      assert_eq!(cb.as_single_slice(), Some([1,2,3].as_ref()));
+      cb.push_back(4);
+      cb.push_back(5);
+      assert_eq!(cb.as_single_slice(), None);
+    }
+    ```
+    */
+    pub fn as_single_slice(&self) -> Option<&[T]> {
+        if self.is_contiguous() {
+            Some(self.slices().0)
+        } else {
+            None
+        }
+    }
+
+    /**
+    Makes content of the buffer continuous and returns it as one mutable slice.
+
+    Unlike `linearize()` elements are moved only if the content wraps around the end
+    of the internal buffer. Elements are rotated in place without allocating memory.
+
+    **Complexity:** O(n) in the worst case, O(1) if the buffer is already continuous.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.push_back(4);
+      cb.push_back(5);
+      cb.make_contiguous().sort_by(|a, b| b.cmp(a));
+      assert!(cb.is_contiguous());
+      assert_eq!(cb, [5,4,3].as_ref());
+    }
+    ```
+    */
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.is_contiguous() {
+            self.slices_mut().0
+        } else {
+            self.linearize()
+        }
+    }
+
+    /**
+    Swaps places of two elements in the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.swap(0,1);
+      assert_eq!(cb, [2,1,3].as_ref());
+    }
+    ```
+    */
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.ring.swap(a, b);
+    }
+
+    /**
+    Copies elements into the provided slice, returning the number of copied elements.
+
+    If the slice is shorter than the buffer, only the newest elements are copied.
+    Elements are copied in order from the front to back using at most two memory copies
+    and the buffer is not modified.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let mut snapshot = [0; 3];
+      assert_eq!(cb.copy_newest_to_slice(&mut snapshot), 3);
+      assert_eq!(snapshot, [4,5,6]);
+      let mut all = [0; 6];
+      assert_eq!(cb.copy_newest_to_slice(&mut all), 4);
+      assert_eq!(all, [3,4,5,6,0,0]);
+    }
+    ```
+    */
+    pub fn copy_newest_to_slice(&self, dst: &mut [T]) -> usize where T: Copy {
+        let n = self.len().min(dst.len());
+        let skip = self.len() - n;
+        let (a, b) = self.slices();
+        if skip < a.len() {
+            let a = &a[skip..];
+            dst[..a.len()].copy_from_slice(a);
+            dst[a.len()..n].copy_from_slice(b);
+        } else {
+            dst[..n].copy_from_slice(&b[skip - a.len()..]);
+        }
+        n
+    }
+
+    /**
+    Moves up to `dst.len()` elements from the front of the buffer into the slice.
+
+    Elements are copied with at most two memory copies and removed from the buffer,
+    which makes it suitable for draining a buffer into caller-provided memory.
+    Unlike `copy_newest_to_slice()`, it reads the oldest elements.
+    Returns the number of moved elements.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let mut block = [0; 3];
+      assert_eq!(cb.read_into(&mut block), 3);
+      assert_eq!(block, [3,4,5]);
+      assert_eq!(cb.read_into(&mut block), 1);
+      assert_eq!(block[0], 6);
+      assert!(cb.is_empty());
+    }
+    ```
+    */
+    pub fn read_into(&mut self, dst: &mut [T]) -> usize where T: Copy {
+        let n = {
+            let (a, b) = self.slices();
+            let n1 = a.len().min(dst.len());
+            dst[..n1].copy_from_slice(&a[..n1]);
+            let n2 = b.len().min(dst.len() - n1);
+            dst[n1..n1 + n2].copy_from_slice(&b[..n2]);
+            n1 + n2
+        };
+        //Copy types do not need to be dropped, the slots can be just released
+        unsafe { self.ring.forget_front(n) };
+        self.seq = self.seq.wrapping_add(n as u64);
+        self.totals.popped = self.totals.popped.wrapping_add(n as u64);
+        n
+    }
+
+    /**
+    Moves exactly `dst.len()` elements from the front of the buffer into the slice.
+
+    Works like `read_into()` but requires the buffer to contain enough elements,
+    the same way as `bytes::Buf::copy_to_slice()` does.
+
+    # Panics
+
+    Panics if the buffer contains fewer than `dst.len()` elements.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let mut block = [0; 3];
+      cb.copy_to_slice(&mut block);
+      assert_eq!(block, [3,4,5]);
+      assert_eq!(cb.len(), 1);
+    }
+    ```
+    */
+    pub fn copy_to_slice(&mut self, dst: &mut [T]) where T: Copy {
+        assert!(dst.len() <= self.len(), "not enough elements in the buffer");
+        self.read_into(dst);
+    }
+
+    /**
+    Returns the free slots of the buffer, so that elements can be written into them directly.
+
+    The first slice starts right after the back of the buffer, the second one continues
+    from the beginning of the internal storage when the free space wraps around.
+    Written elements become a part of the buffer only after calling `commit()`.
+    Memory of a buffer created without allocation is allocated by this function.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::io::Read;
+
+    fn main(){
+        let mut cb = CircularBuffer::new(8);
+        cb.extend(b"abcdef".iter().cloned());
+        cb.read_into(&mut [0; 4]);
+        let mut input: &[u8] = b"ghijkl";
+        let mut read = 0;
+        {
+            let (a, b) = cb.spare_capacity_mut();
+            for chunk in [a, b].iter_mut() {
+                for slot in chunk.iter_mut() {
+                    let mut byte = [0];
+                    if input.read(&mut byte).unwrap() == 0 {
+                        break;
+                    }
+                    slot.write(byte[0]);
+                    read += 1;
+                }
+            }
+        }
+        unsafe { cb.commit(read) };
+        assert_eq!(cb, b"efghijkl".as_ref());
+    }
+    ```
+    */
+    pub fn spare_capacity_mut(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        self.allocate();
+        self.ring.spare_capacity_mut()
+    }
+
+    /**
+    Appends `n` elements written into the slots returned by `spare_capacity_mut()` to the back of the buffer.
+
+    # Panics
+
+    Panics if `n` is greater than the number of free slots.
+
+    # Safety
+
+    The first `n` slots returned by `spare_capacity_mut()`, counting from the beginning of
+    the first slice and continuing in the second one, must be initialized.
+    */
+    pub unsafe fn commit(&mut self, n: usize) {
+        let free = self.capacity() - self.len();
+        if n > free {
+            panic!("Cannot commit {} elements, only {} slots are free.", n, free);
+        }
+        self.ring.commit(n);
+        self.totals.pushed = self.totals.pushed.wrapping_add(n as u64);
+    }
+
+    /**
+    Replaces all stored elements with clones of the value.
+
+    The number of elements does not change.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(vec![1,2,3]);
+      cb.fill(0);
+      assert_eq!(cb, [0,0,0].as_ref());
+    }
+    ```
+    */
+    pub fn fill(&mut self, val: T) where T: Clone {
+        let (a, b) = self.slices_mut();
+        a.fill(val.clone());
+        b.fill(val);
+    }
+
+    /**
+    Changes the number of stored elements, keeping the capacity.
+
+    New elements are generated by the provided function and placed at the end of the buffer.
+    If the buffer contains more elements, elements from the end are removed.
+    Panics if the number of elements is greater than the capacity.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(5);
+      cb.extend(vec![1,2]);
+      let mut next = 10;
+      cb.resize_len_with(4, || { next += 1; next });
+      assert_eq!(cb, [1,2,11,12].as_ref());
+      cb.resize_len_with(1, || 0);
+      assert_eq!(cb, [1].as_ref());
+    }
+    ```
+    */
+    pub fn resize_len_with<F>(&mut self, len: usize, mut f: F) where F: FnMut() -> T {
+        assert!(len <= self.capacity(), "Number of elements is greater than the capacity.");
+        if len > 0 {
+            self.allocate();
+        }
+        while self.len() > len {
+            self.take_back();
+        }
+        while self.len() < len {
+            self.ring.push_back(f());
+            self.totals.pushed = self.totals.pushed.wrapping_add(1);
+        }
+    }
+
+    /**
+    Checks if the buffer contains an element equal to the given value.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3]);
+      assert!(cb.contains(&2));
+      assert!(!cb.contains(&4));
+    }
+    ```
+    */
+    pub fn contains(&self, val: &T) -> bool where T: PartialEq {
+        let (a, b) = self.slices();
+        a.contains(val) || b.contains(val)
+    }
+
+    /**
+    Binary searches a sorted buffer for the given element.
+
+    Behaves like `binary_search()` of slices, searching both internal slices without linearizing them.
+    If the value is found, returns `Ok` with its index, otherwise `Err` with the index where it could be
+    inserted to keep the buffer sorted.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(vec![1,3,5,7,9]);
+      assert_eq!(cb.binary_search(&7), Ok(2));
+      assert_eq!(cb.binary_search(&4), Err(1));
+    }
+    ```
+    */
+    pub fn binary_search(&self, val: &T) -> Result<usize, usize> where T: Ord {
+        self.binary_search_by(|el| el.cmp(val))
+    }
+
+    /**
+    Binary searches a sorted buffer with a comparator function.
+
+    The comparator returns the ordering of the element relative to the searched one.
+    */
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize> where F: FnMut(&T) -> Ordering {
+        let (a, b) = self.slices();
+        match b.first() {
+            Some(first) if f(first) != Ordering::Greater => b.binary_search_by(f)
+                .map(|idx| idx + a.len())
+                .map_err(|idx| idx + a.len()),
+            _ => a.binary_search_by(f)
+        }
+    }
+
+    ///Binary searches a sorted buffer with a key extraction function.
+    pub fn binary_search_by_key<B, F>(&self, key: &B, mut f: F) -> Result<usize, usize> where B: Ord, F: FnMut(&T) -> B {
+        self.binary_search_by(|el| f(el).cmp(key))
+    }
+
+    /**
+    Rotates the buffer `n` places to the left, so that the element at index `n` becomes the first one.
+
+    Only `min(n, len - n)` elements are moved and no memory is allocated.
+    Sequence numbers of the front and the end of the buffer do not change.
+    Panics if `n` is greater than the number of elements.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      //round-robin scheduling of workers
+      let mut workers = CircularBuffer::from(vec![1,2,3]);
+      workers.rotate_left(1);
+      assert_eq!(workers, [2,3,1].as_ref());
+    }
+    ```
+    */
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len(), "Rotation is larger than the number of elements.");
+        if n <= self.len() - n {
+            self.rotate_front_to_back(n);
+        } else {
+            self.rotate_back_to_front(self.len() - n);
+        }
+    }
+
+    /**
+    Rotates the buffer `n` places to the right, so that the first element gets index `n`.
+
+    Only `min(n, len - n)` elements are moved and no memory is allocated.
+    Sequence numbers of the front and the end of the buffer do not change.
+    Panics if `n` is greater than the number of elements.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3,4]);
+      cb.rotate_right(1);
+      assert_eq!(cb, [4,1,2,3].as_ref());
+    }
+    ```
+    */
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len(), "Rotation is larger than the number of elements.");
+        if n <= self.len() - n {
+            self.rotate_back_to_front(n);
+        } else {
+            self.rotate_front_to_back(self.len() - n);
+        }
+    }
+
+    /**
+    Reverses order of elements in the buffer.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3]);
+      cb.reverse();
+      assert_eq!(cb, [3,2,1].as_ref());
+    }
+    ```
+    */
+    pub fn reverse(&mut self) {
+        for a in 0..self.len()/2 {
+            let b = self.len() - a - 1;
+            self.swap(a, b);
+        }
+    }
+
+    /**
+    Returns an iterator over fixed-size frames of the buffer content, from the front to back.
+
+    Each frame is represented by two slices, because a frame can span across the end
+    of the internal buffer. The last frame may be shorter than `frame_len`.
+    The buffer is not linearized, so this method can be used on immutable buffers.
+
+    Panics if `frame_len` is 0.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::iter::FromIterator;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![1,2,3,4]);
+      cb.push_back(5);
+      cb.push_back(6);
+      let frames = Vec::from_iter(cb.to_frames(3).map(|(a, b)| [a, b].concat()));
+      assert_eq!(frames, vec![vec![3,4,5], vec![6]]);
+    }
+    ```
+    */
+    pub fn to_frames(&self, frame_len: usize) -> Chunks<'_, T> {
+        let (a, b) = self.slices();
+        Chunks::new(a, b, frame_len)
+    }
+
+    /**
+    Returns an iterator over chunks of the buffer content, from the front to back.
+
+    This is the same iterator as the one returned by `to_frames()`, named after `slice::chunks()`.
+    The last chunk may be shorter than `chunk_len`.
+
+    Panics if `chunk_len` is 0.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3,4,5]);
+      let v: Vec<Vec<i32>> = cb.chunks(2).map(|(a, b)| [a, b].concat()).collect();
+      assert_eq!(v, vec![vec![1,2], vec![3,4], vec![5]]);
     }
     ```
     */
-    pub fn slices(&self) -> (&[T], &[T]){
-        let (a,b) = if self.start <= self.end {
-            (&self.buffer[self.start..self.end], &self.buffer[0..0])
-        } else {
-            (&self.buffer[self.start..], &self.buffer[..self.end])
-        };
+    pub fn chunks(&self, chunk_len: usize) -> Chunks<'_, T> {
+        self.to_frames(chunk_len)
+    }
+
+    /**
+    Returns an iterator over all overlapping windows of the buffer content, from the front to back.
+
+    Each window is represented by two slices, the same way as in `to_frames()`,
+    so windows crossing the end of the internal buffer are not copied.
+    No windows are returned if the buffer contains fewer than `window_len` elements.
+
+    Panics if `window_len` is 0.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
 
-        //ManuallyDrop is a zero-cost wrapper, can be safely converted into slice of T
-        unsafe{(transmute(a), transmute(b))}
+    fn main(){
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(0..6);
+      //moving sums of 3 samples
+      let sums: Vec<i32> = cb.windows(3).map(|(a, b)| a.iter().chain(b).sum()).collect();
+      assert_eq!(sums, vec![2+3+4, 3+4+5]);
+    }
+    ```
+    */
+    pub fn windows(&self, window_len: usize) -> Windows<'_, T> {
+        let (a, b) = self.slices();
+        Windows::new(a, b, window_len)
     }
 
     /**
-    Returns two mutable slices to the internal buffer.
+    Returns an iterator yielding exactly `len` values: clones of the buffer content,
+    from the front to back, followed by clones of `pad`.
 
-    Because the internal buffer is circular, normally it is not possible to represent it
-    as a single slice of data, but it is possible to represent it as two slices -
-    one after another.
+    If the buffer contains more than `len` elements, only the first `len` of them are yielded,
+    use `range()` to select other elements. This produces fixed-size inputs,
+    e.g. zero-padded FFT frames, without copying the content into a temporary vector.
 
     # Example
 
@@ -600,32 +2049,60 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      cb.push_back(4);
-      cb.push_back(5);
-      let (mut a, mut b) = cb.slices_mut();
-      a[0] = 4;
-      a[1] = 5;
-      b[0] = 6;
-      assert_eq!(cb, [4,5,6].as_ref());
+      let mut cb = CircularBuffer::new(4);
+      cb.extend(1..7);
+      let mut frame = [-1; 8];
+      for (dst, val) in frame.iter_mut().zip(cb.padded_view(8, 0)) {
+        *dst = val;
+      }
+      assert_eq!(frame, [3, 4, 5, 6, 0, 0, 0, 0]);
+      assert_eq!(cb.padded_view(2, 0).collect::<Vec<_>>(), vec![3, 4]);
     }
     ```
     */
-    pub fn slices_mut(&mut self) -> (&mut[T], &mut [T]) {
-        let (a,b) = if self.start <= self.end {
-            let (x, y) = self.buffer.split_at_mut(self.end);
-            (&mut x[self.start..self.end], &mut y[0..0])
-        } else {
-            let (x, y) = self.buffer.split_at_mut(self.start);
-            (y,  &mut x[..self.end])
-        };
+    pub fn padded_view(&self, len: usize, pad: T) -> PaddedView<'_, T> {
+        let (a, b) = self.slices();
+        PaddedView::new(a, b, len, pad)
+    }
+
+    /**
+    Returns an iterator over complete chunks of the buffer content, from the front to back.
 
-        //ManuallyDrop is a zero-cost wrapper, can be safely converted into slice of T
-        unsafe{(transmute(a), transmute(b))}
+    Each chunk is represented by two slices, the same way as in `to_frames()`.
+    Trailing elements that do not form a complete chunk are skipped,
+    their number is reported by `ChunksExact::remainder_len()`.
+
+    Panics if `chunk_len` is 0.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let cb = CircularBuffer::from(vec![1,2,3,4,5]);
+      let chunks = cb.chunks_exact(2);
+      assert_eq!(chunks.remainder_len(), 1);
+      let v: Vec<Vec<i32>> = chunks.map(|(a, b)| [a, b].concat()).collect();
+      assert_eq!(v, vec![vec![1,2], vec![3,4]]);
+    }
+    ```
+    */
+    pub fn chunks_exact(&self, chunk_len: usize) -> ChunksExact<'_, T> {
+        let (a, b) = self.slices();
+        ChunksExact::new(a, b, chunk_len)
     }
 
     /**
-    Rearranges content of the buffer to achieve a continuous region.
+    Returns the sequence number of the first element of the buffer.
+
+    Every element gets a sequence number when it is pushed into the buffer.
+    Sequence numbers grow by one for each element pushed to the back,
+    so the n-th element of the buffer has the sequence number `front_seq() + n`.
+    Elements removed from the front, including those overwritten by pushing into
+    a full buffer, never give their sequence numbers back. Elements removed from
+    the back do, so the next element pushed to the back reuses the sequence number
+    of the last removed one.
 
     # Example
 
@@ -633,129 +2110,394 @@ impl<T> CircularBuffer<T> {
     use advanced_collections::circular_buffer::CircularBuffer;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      cb.push_back(4);
-      cb.push_back(5);
-      //slices() would now return [3,4], [5]
-      assert_eq!(cb.linearize(), [3,4,5].as_ref());
+      let mut cb: CircularBuffer<i32> = CircularBuffer::new(2);
+      cb.extend(&[1, 2, 3]);
+      assert_eq!(cb.front_seq(), 1);
+      assert_eq!(cb.next_seq(), 3);
     }
     ```
     */
-    pub fn linearize(&mut self) -> &mut [T]{
-        self.buffer.rotate_left(self.start);
-        self.end = self.len();
-        self.start = 0;
-        //ManuallyDrop is a zero-cost wrapper, can be safely converted into slice of T
-        unsafe{transmute(&mut self.buffer[..self.end])}
+    pub fn front_seq(&self) -> u64 {
+        self.seq
+    }
+
+    /**
+    Returns the sequence number that the next element pushed to the back will get.
+    */
+    pub fn next_seq(&self) -> u64 {
+        self.seq.wrapping_add(self.len() as u64)
+    }
+
+    /**
+    Returns the number of elements added since the creation of the buffer or the last `reset_totals()`.
+
+    Elements added by `push_*()` functions, extending, `append()`, `resize_len_with()`, `commit()`
+    and writing through `std::io::Write` or `bytes::BufMut` are counted.
+    Elements rejected by a full buffer are not counted, nor are elements added by constructors
+    or moved inside the buffer by resizing, rotating, retaining and deduplication.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(1..6);
+        cb.pop_front();
+        assert_eq!(cb.total_pushed(), 5);
+        assert_eq!(cb.total_evicted(), 2);
+        assert_eq!(cb.total_popped(), 1);
+        cb.reset_totals();
+        assert_eq!(cb.total_pushed(), 0);
+    }
+    ```
+    */
+    pub fn total_pushed(&self) -> u64 {
+        self.totals.pushed
+    }
+
+    ///Returns the number of elements removed by pushes into a full buffer.
+    pub fn total_evicted(&self) -> u64 {
+        self.totals.evicted
     }
 
+    /**
+    Returns the number of elements removed by `pop_*()` functions, `read_into()`, `copy_to_slice()` and draining.
+
+    Elements removed by `clear()`, truncation or resizing are not counted.
+    */
+    pub fn total_popped(&self) -> u64 {
+        self.totals.popped
+    }
 
+    ///Sets all totals to 0.
+    pub fn reset_totals(&mut self) {
+        self.totals = Totals::default();
+    }
 
     /**
-    Swaps places of two elements in the buffer.
+    Returns an iterator over elements with sequence numbers equal or greater than `seq`,
+    together with their sequence numbers.
+
+    The buffer is not modified, so multiple independent readers can poll the same buffer.
+    Each reader should remember `next_seq()` after reading and pass it to the next call.
+    If some elements were already removed from the buffer, the iteration starts from the first one.
+
+    Reader cursors are only reliable while elements are pushed to the back and removed from the front.
+    `pop_back()`, `push_front()` and other operations that remove elements from the back or add them
+    to the front renumber the following elements: an element pushed after `pop_back()` reuses
+    the sequence number of the popped one, so readers that already read the popped element
+    never see the new one.
 
     # Example
 
     ```
     use advanced_collections::circular_buffer::CircularBuffer;
+    use std::iter::FromIterator;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      cb.swap(0,1);
-      assert_eq!(cb, [2,1,3].as_ref());
+      let mut cb = CircularBuffer::new(3);
+      cb.extend(&[10, 11]);
+
+      let mut reader = 0;
+      assert_eq!(Vec::from_iter(cb.iter_since(reader)), vec![(0, &10), (1, &11)]);
+      reader = cb.next_seq();
+
+      cb.extend(&[12, 13]);
+      assert_eq!(Vec::from_iter(cb.iter_since(reader)), vec![(2, &12), (3, &13)]);
+
+      //a reader that is too slow misses elements that were overwritten
+      assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(1, &11), (2, &12), (3, &13)]);
     }
     ```
     */
-    pub fn swap(&mut self, a: usize, b: usize) {
-        self.buffer.swap(self.internal_index(a), self.internal_index(b));
+    pub fn iter_since(&self, seq: u64) -> impl Iterator<Item=(u64, &T)> + '_ {
+        //the distance can be negative if the reader is behind the buffer
+        let offset = seq.wrapping_sub(self.seq) as i64;
+        let skip = if offset < 0 {
+            0
+        } else {
+            (offset as u64).min(self.len() as u64) as usize
+        };
+        let first = self.seq.wrapping_add(skip as u64);
+        self.iter()
+            .skip(skip)
+            .enumerate()
+            .map(move |(i, val)| (first.wrapping_add(i as u64), val))
     }
 
     /**
-    Reverses order of elements in the buffer.
+    Keeps only elements whose indices belong to the mask, removing all others.
+
+    Retained elements keep their relative order and the buffer is compacted in place.
+    Sequence numbers are updated as if removed elements were popped from the front,
+    so `next_seq()` does not change.
 
     # Example
 
     ```
     use advanced_collections::circular_buffer::CircularBuffer;
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
 
     fn main(){
-      let mut cb = CircularBuffer::from(vec![1,2,3]);
-      cb.reverse();
-      assert_eq!(cb, [3,2,1].as_ref());
+      let mut cb = CircularBuffer::from(vec![10, 11, 12, 13, 14, 15]);
+      let mask = IntervalSet::from_iter(vec![Interval::closed(1, 2), Interval::lower_closed(4, 10)]);
+      cb.retain_logical(&mask);
+      assert_eq!(cb, [11, 12, 14, 15].as_ref());
     }
     ```
     */
-    pub fn reverse(&mut self) {
-        for a in 0..self.len()/2 {
-            let b = self.len() - a - 1;
-            self.buffer.swap(self.internal_index(a), self.internal_index(b));
+    pub fn retain_logical(&mut self, mask: &IntervalSet<usize>) {
+        let len = self.len();
+        let next_seq = self.next_seq();
+        for idx in 0..len {
+            //there is always space at the back, because one element was just removed
+            let val = self.ring.pop_front().unwrap();
+            if mask.contains_val(&idx) {
+                self.ring.push_back(val);
+            }
+        }
+        self.seq = next_seq.wrapping_sub(self.len() as u64);
+    }
+
+    /**
+    Removes consecutive elements for which the function returns `true`, keeping the first one of each run.
+
+    The function gets the current element and the previously retained one, like `Vec::dedup_by()`.
+    The buffer is compacted in place, also across the end of the internal buffer.
+    Sequence numbers are updated the same way as in `retain_logical()`.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      //readings that differ by less than 2 collapse into one
+      let mut cb = CircularBuffer::from(vec![10i32, 11, 15, 16, 17, 30]);
+      cb.dedup_by(|a, b| (*a - *b).abs() < 2);
+      assert_eq!(cb, [10, 15, 17, 30].as_ref());
+    }
+    ```
+    */
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F) where F: FnMut(&mut T, &mut T) -> bool {
+        let len = self.len();
+        if len < 2 {
+            return;
         }
+        let next_seq = self.next_seq();
+        //the first element is always retained
+        let first = self.ring.pop_front().unwrap();
+        self.ring.push_back(first);
+        for _ in 1..len {
+            //there is always space at the back, because one element was just removed
+            let mut val = self.ring.pop_front().unwrap();
+            let last = self.ring.last_mut().unwrap();
+            if !same_bucket(&mut val, last) {
+                self.ring.push_back(val);
+            }
+        }
+        self.seq = next_seq.wrapping_sub(self.len() as u64);
+    }
+
+    /**
+    Removes consecutive elements that map to the same key.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::from(vec![10, 12, 21, 25, 13]);
+      cb.dedup_by_key(|x| *x / 10);
+      assert_eq!(cb, [10, 21, 13].as_ref());
+    }
+    ```
+    */
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F) where F: FnMut(&mut T) -> K, K: PartialEq {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /**
+    Removes consecutive equal elements, e.g. repeated identical readings of a sensor.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+
+    fn main(){
+      let mut cb = CircularBuffer::new(8);
+      cb.extend(vec![1, 1, 2, 2, 2, 3, 1, 1]);
+      cb.dedup();
+      assert_eq!(cb, [1, 2, 3, 1].as_ref());
+    }
+    ```
+    */
+    pub fn dedup(&mut self) where T: PartialEq {
+        self.dedup_by(|a, b| a == b)
     }
 
 //private helpers
 
-    fn internal_index(&self, index: usize) -> usize {
-        if index >= self.len() {
-            panic!("Index outside of bound of CircularBuffer");
+    //doubles the capacity of a full buffer if it has not reached its growth limit
+    fn grow(&mut self) -> bool {
+        let capacity = self.capacity();
+        if capacity >= self.max_capacity {
+            return false;
         }
-        if self.start + index < self.buffer.len(){
-            self.start + index
-        } else {
-            index + self.start - self.buffer.len()
+        self.resize(capacity.saturating_mul(2).clamp(1, self.max_capacity));
+        true
+    }
+
+    //allocates memory of a buffer created without it, when the first element is stored
+    fn allocate(&mut self) {
+        if let Some(capacity) = self.lazy_capacity {
+            if capacity > 0 {
+                self.resize(capacity);
+            }
         }
+    }
 
+    //uninitialized slots of a new storage, zero-sized types never allocate so their slots are created in O(1)
+    fn slots(capacity: usize) -> Box<[MaybeUninit<T>]> {
+        let mut slots = Vec::with_capacity(capacity);
+        //MaybeUninit slots do not need to be initialized
+        unsafe { slots.set_len(capacity) };
+        slots.into_boxed_slice()
     }
 
-    fn incr_end(&mut self) {
-        debug_assert!(!self.is_full());
-        self.end += 1;
-        if self.end == self.buffer.len(){
-            self.end = 0;
+    fn from_ring(ring: GenericCircularBuffer<T, Box<[MaybeUninit<T>]>>) -> Self {
+        Self {
+            ring,
+            seq: 0,
+            policy: EvictionPolicy::Overwrite,
+            max_capacity: 0,
+            on_evict: OnEvict::default(),
+            lazy_capacity: None,
+            totals: Totals::default()
         }
     }
 
-    fn decr_end(&mut self) {
-        debug_assert!(!self.is_empty());
-        self.end = if self.end == 0 {
-            self.buffer.len() - 1
-        } else {
-            self.end - 1
+    //replaces the ring with an empty one without memory
+    fn take_ring(&mut self) -> GenericCircularBuffer<T, Box<[MaybeUninit<T>]>> {
+        replace(&mut self.ring, GenericCircularBuffer::from_storage(Self::slots(0)))
+    }
+
+    //copies everything except elements and their memory from a buffer that is being cloned
+    fn copy_settings(&mut self, other: &Self) {
+        self.seq = other.seq;
+        self.policy = other.policy;
+        self.max_capacity = other.max_capacity;
+        self.on_evict = OnEvict::default();
+        self.totals = other.totals;
+    }
+
+    //passes an element removed by an overwriting push to the callback
+    fn evicted(&mut self, val: Option<T>) {
+        if val.is_some() {
+            self.totals.evicted = self.totals.evicted.wrapping_add(1);
+        }
+        if let (Some(f), Some(val)) = (self.on_evict.0.as_mut(), val) {
+            f(val);
         }
     }
 
-    fn incr_start(&mut self) {
-        debug_assert!(!self.is_empty());
-        self.start +=1;
-        if self.start == self.buffer.len() {
-            self.start = 0;
+    //decides if an element can be removed to make space in a full buffer
+    fn make_space(&self) -> bool {
+        match self.policy {
+            EvictionPolicy::Overwrite => self.capacity() > 0,
+            EvictionPolicy::Reject => false,
+            EvictionPolicy::Panic => panic!("Pushing into a full CircularBuffer.")
         }
     }
 
-    fn decr_start(&mut self){
-        debug_assert!(!self.is_full());
-        self.start = if self.start == 0 {
-            self.buffer.len() - 1
-        } else {
-            self.start - 1
+    //removes the front element without counting it as popped
+    fn take_front(&mut self) -> Option<T> {
+        let val = self.ring.pop_front();
+        if val.is_some() {
+            self.seq = self.seq.wrapping_add(1);
+        }
+        val
+    }
+
+    //removes the back element without counting it as popped
+    fn take_back(&mut self) -> Option<T> {
+        self.ring.pop_back()
+    }
+
+    //moves n elements from the front to the back, there is always a free slot to move them to
+    fn rotate_front_to_back(&mut self, n: usize) {
+        for _ in 0..n {
+            let val = self.ring.pop_front().expect("Rotation is not larger than the number of elements");
+            self.ring.push_back(val);
         }
     }
 
-    fn pop_at(&mut self, index: usize) -> T {
-        //replace place in the array with uninitialized object
-        let mut tmp = ManuallyDrop::new(unsafe{uninitialized()});
-        swap(&mut self.buffer[index], &mut tmp);
-        ManuallyDrop::into_inner(tmp)
+    fn rotate_back_to_front(&mut self, n: usize) {
+        for _ in 0..n {
+            let val = self.ring.pop_back().expect("Rotation is not larger than the number of elements");
+            self.ring.push_front(val);
+        }
     }
+}
 
-    fn push_at(&mut self, val: T, index: usize) {
-        //the replaced value is unitialized, so it should not be dropped
-        self.buffer[index] = ManuallyDrop::new(val);
+/**
+Creates an empty buffer with zero capacity that does not allocate any memory.
+
+Memory is allocated when the first element is pushed after `set_capacity()`
+or immediately by `resize()`.
+*/
+impl<T> Default for CircularBuffer<T> {
+    fn default() -> Self {
+        let mut cb = Self::with_capacity(0);
+        cb.lazy_capacity = Some(0);
+        cb
     }
 }
 
-impl<T> Drop for CircularBuffer<T> {
-    fn drop(&mut self) {
-        self.clear();
+/**
+Clones only the stored elements into new memory of the same capacity.
+
+Sequence numbers, the eviction policy and the growth limit are copied,
+the function registered by `set_on_evict()` is not.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+
+fn main(){
+    let mut cb = CircularBuffer::new(3);
+    cb.extend(1..5);
+    let copy = cb.clone();
+    assert_eq!(copy, cb);
+    assert_eq!(copy.capacity(), 3);
+    assert_eq!(copy.front_seq(), cb.front_seq());
+
+    let mut reused = CircularBuffer::new(3);
+    reused.clone_from(&cb);
+    assert_eq!(reused, [2,3,4].as_ref());
+}
+```
+*/
+impl<T> Clone for CircularBuffer<T> where T: Clone {
+    fn clone(&self) -> Self {
+        let mut res = Self::default();
+        res.set_capacity(self.capacity());
+        res.extend(self.iter().cloned());
+        res.copy_settings(self);
+        res
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        //reuses the allocation if the capacity is the same
+        self.recycle(source.capacity());
+        self.extend(source.iter().cloned());
+        self.copy_settings(source);
     }
 }
 
@@ -763,61 +2505,101 @@ impl <T> Index<usize> for CircularBuffer<T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &<Self as Index<usize>>::Output {
-        &*self.buffer[self.internal_index(index)]
+        self.get(index).expect("Index outside of bound of CircularBuffer")
     }
 }
 
 impl <T> IndexMut<usize> for CircularBuffer<T> {
     fn index_mut(&mut self, index: usize) -> &mut <Self as Index<usize>>::Output {
-        &mut *self.buffer[self.internal_index(index)]
+        self.get_mut(index).expect("Index outside of bound of CircularBuffer")
     }
 }
 
-impl <T> fmt::Debug for CircularBuffer<T> {
+impl <T> fmt::Debug for CircularBuffer<T> where T: fmt::Debug {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CircularBuffer{{ start: {}, end: {}, buf_len: {} }}", self.start, self.end, self.buffer.len())
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
 impl <T> FromIterator<T> for CircularBuffer<T>{
     fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
-        let mut buf = Vec::from_iter(iter.into_iter().map(|x| ManuallyDrop::new(x)));
-        buf.push(unsafe{uninitialized()});
-        buf.shrink_to_fit();
-
-        let end = buf.len() -1;
-        Self {
-            buffer: buf.into_boxed_slice(),
-            start: 0,
-            end
-        }
+        Self::from(Vec::from_iter(iter))
     }
 }
 
 impl <'a, T> FromIterator<&'a T> for CircularBuffer<T> where T: Clone{
     fn from_iter<I: IntoIterator<Item=&'a T>>(iter: I) -> Self {
-        let mut buf = Vec::from_iter(iter.into_iter().map(|x| ManuallyDrop::new(x.clone())));
-        buf.push(unsafe{uninitialized()});
-        buf.shrink_to_fit();
-        let end = buf.len() -1;
-        Self {
-            buffer: buf.into_boxed_slice(),
-            start: 0,
-            end
-        }
+        Self::from(Vec::from_iter(iter.into_iter().cloned()))
     }
 }
 
 impl<T> From<Vec<T>> for CircularBuffer<T>{
-    fn from(mut v : Vec<T>) -> Self {
-        let buf_len = v.len();
-        v.push(unsafe{uninitialized()});
-        v.shrink_to_fit();
-        Self{
-            buffer: unsafe{transmute(v.into_boxed_slice())},
-            start: 0,
-            end: buf_len
+    fn from(v : Vec<T>) -> Self {
+        let len = v.len();
+        //MaybeUninit<T> has the same layout as T, the elements are owned by the buffer from now on
+        let slots = unsafe { Box::from_raw(Box::into_raw(v.into_boxed_slice()) as *mut [MaybeUninit<T>]) };
+        Self::from_ring(unsafe { GenericCircularBuffer::from_initialized(slots, len) })
+    }
+}
+
+/**
+Moves elements into a `VecDeque`, preserving their order.
+
+Elements are moved with two memory copies, without cloning or pushing them one by one.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use std::collections::VecDeque;
+
+fn main(){
+    let mut cb = CircularBuffer::new(3);
+    cb.extend(vec![1, 2, 3, 4]);
+    let mut deque = VecDeque::from(cb);
+    deque.push_back(5);
+    assert_eq!(deque, vec![2, 3, 4, 5]);
+}
+```
+*/
+impl<T> From<CircularBuffer<T>> for VecDeque<T>{
+    fn from(mut cb: CircularBuffer<T>) -> Self {
+        let len = cb.len();
+        let mut v: Vec<T> = Vec::with_capacity(len);
+        {
+            let (a, b) = cb.slices();
+            unsafe {
+                ptr::copy_nonoverlapping(a.as_ptr(), v.as_mut_ptr(), a.len());
+                ptr::copy_nonoverlapping(b.as_ptr(), v.as_mut_ptr().add(a.len()), b.len());
+                v.set_len(len);
+            }
         }
+        //elements were moved into the vector, so the buffer must not drop them
+        unsafe { cb.ring.forget_front(len) };
+        VecDeque::from(v)
+    }
+}
+
+/**
+Creates a full buffer with the capacity equal to the number of elements in the `VecDeque`.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use std::collections::VecDeque;
+
+fn main(){
+    let deque: VecDeque<i32> = (1..4).collect();
+    let cb = CircularBuffer::from(deque);
+    assert!(cb.is_full());
+    assert_eq!(cb, [1, 2, 3].as_ref());
+}
+```
+*/
+impl<T> From<VecDeque<T>> for CircularBuffer<T>{
+    fn from(deque: VecDeque<T>) -> Self {
+        Self::from(Vec::from(deque))
     }
 }
 
@@ -887,10 +2669,50 @@ impl<T> PartialEq<[T]> for CircularBuffer<T>
 
 impl<'a, T> PartialEq<&'a [T]> for CircularBuffer<T>
 
-    where T: PartialEq
+    where T: PartialEq
+{
+    fn eq(&self, other: &&'a [T]) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> PartialEq<Vec<T>> for CircularBuffer<T>
+
+    where T: PartialEq
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T, const N: usize> PartialEq<[T; N]> for CircularBuffer<T>
+
+    where T: PartialEq
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> PartialEq<VecDeque<T>> for CircularBuffer<T>
+
+    where T: PartialEq
+{
+    fn eq(&self, other: &VecDeque<T>) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+///Equal buffers have equal hashes, regardless of the layout of their elements in memory.
+impl<T> Hash for CircularBuffer<T>
+
+    where T: Hash
 {
-    fn eq(&self, other: &&'a [T]) -> bool {
-        self.iter().eq(other.iter())
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for el in self.iter() {
+            el.hash(state);
+        }
     }
 }
 
@@ -920,6 +2742,7 @@ impl<T> Ord for CircularBuffer<T>
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
+    use crate::interval::Interval;
 
     fn  cb_eq<T>(cb:&CircularBuffer<T>, exp: &[T]) -> bool where T:Eq {
         cb.iter().eq(exp.iter())
@@ -1129,11 +2952,11 @@ mod tests {
         cb.push_back(4);
         cb.push_back(5);
         let (a,b) = cb.slices();
-        assert_eq!(a, &[3,4]);
-        assert_eq!(b, &[5]);
+        assert_eq!(a, &[3]);
+        assert_eq!(b, &[4,5]);
         let (a,b) = cb.slices_mut();
-        assert_eq!(a, &[3,4]);
-        assert_eq!(b, &[5]);
+        assert_eq!(a, &[3]);
+        assert_eq!(b, &[4,5]);
 
     }
 
@@ -1209,4 +3032,910 @@ mod tests {
         let c3 = CircularBuffer::from(vec![2, 3, 4, 5]);
         assert!(c3 > c2)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_to_frames(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(&[1,2,3,4,5,6,7,8,9]);
+        assert_eq!(cb.slices(), ([5].as_ref(), [6,7,8,9].as_ref()));
+        let frames: Vec<Vec<i32>> = cb.to_frames(2).map(|(a, b)| [a, b].concat()).collect();
+        assert_eq!(frames, vec![vec![5,6], vec![7,8], vec![9]]);
+        let frames: Vec<(&[i32], &[i32])> = cb.to_frames(4).collect();
+        assert_eq!(frames, vec![([5].as_ref(), [6,7,8].as_ref()), ([9].as_ref(), [].as_ref())]);
+        assert_eq!(cb.to_frames(5).count(), 1);
+        assert_eq!(cb.to_frames(3).size_hint(), (2, Some(2)));
+        let empty: CircularBuffer<i32> = CircularBuffer::new(3);
+        assert_eq!(empty.to_frames(2).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_to_frames_zero(){
+        let cb = CircularBuffer::from(vec![1,2,3]);
+        cb.to_frames(0);
+    }
+
+    #[test]
+    fn test_from_back(){
+        let mut cb = CircularBuffer::new(3);
+        assert_eq!(cb.from_back(0), None);
+        cb.extend(&[1,2,3,4,5]);
+        assert_eq!(cb.from_back(0), Some(&5));
+        assert_eq!(cb.from_back(1), Some(&4));
+        assert_eq!(cb.from_back(2), Some(&3));
+        assert_eq!(cb.from_back(3), None);
+        *cb.from_back_mut(0).unwrap() = 7;
+        assert_eq!(cb.from_back_mut(3), None);
+        assert!(cb_eq(&cb, &[3,4,7]));
+    }
+
+    #[test]
+    fn test_pop_if(){
+        let mut cb = CircularBuffer::new(4);
+        assert_eq!(cb.pop_front_if(|_| true), None);
+        cb.extend(&[1,2,3,4,5,6]);
+        assert_eq!(cb.pop_front_if(|x| *x > 3), None);
+        assert_eq!(cb.pop_front_if(|x| *x == 3), Some(3));
+        assert_eq!(cb.pop_back_if(|x| *x < 6), None);
+        assert_eq!(cb.pop_back_if(|x| {*x += 1; true}), Some(7));
+        assert_eq!(cb.peek_front(), Some(&4));
+        assert_eq!(cb.peek_back(), Some(&5));
+        assert!(cb_eq(&cb, &[4,5]));
+    }
+
+    #[test]
+    fn test_iter_since(){
+        let mut cb = CircularBuffer::new(3);
+        assert_eq!(cb.iter_since(0).count(), 0);
+        cb.extend(&[1,2,3,4]);
+        assert_eq!(cb.front_seq(), 1);
+        assert_eq!(cb.next_seq(), 4);
+        assert_eq!(Vec::from_iter(cb.iter_since(3)), vec![(3, &4)]);
+        assert_eq!(cb.iter_since(4).count(), 0);
+        assert_eq!(cb.iter_since(100).count(), 0);
+        cb.pop_front();
+        cb.clear();
+        assert_eq!(cb.front_seq(), 4);
+        cb.push_back(5);
+        assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(4, &5)]);
+        //elements pushed to the front get preceding sequence numbers
+        cb.push_front(6);
+        assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(3, &6), (4, &5)]);
+        cb.extend(&[7,8]);
+        cb.resize(1);
+        assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(6, &8)]);
+    }
+
+    #[test]
+    fn test_iter_since_after_pop_back(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(&[1,2,3]);
+        let reader = cb.next_seq();
+        assert_eq!(reader, 3);
+        cb.pop_back();
+        cb.push_back(99);
+        //the new element reuses the sequence number of the popped one, the reader skips it
+        assert_eq!(Vec::from_iter(cb.iter_since(reader)), vec![]);
+        assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(0, &1), (1, &2), (2, &99)]);
+    }
+
+    #[test]
+    fn test_iter_since_wrapping(){
+        let mut cb = CircularBuffer::new(3);
+        cb.push_front(1);
+        cb.push_back(2);
+        assert_eq!(cb.front_seq(), u64::MAX);
+        assert_eq!(Vec::from_iter(cb.iter_since(u64::MAX)), vec![(u64::MAX, &1), (0, &2)]);
+        assert_eq!(Vec::from_iter(cb.iter_since(0)), vec![(0, &2)]);
+    }
+
+    #[test]
+    fn test_for_each_slice_mut(){
+        let mut cb: CircularBuffer<i32> = CircularBuffer::new(4);
+        let mut lens = Vec::new();
+        cb.for_each_slice_mut(|s| lens.push(s.len()));
+        assert!(lens.is_empty());
+        cb.extend(&[1,2,3]);
+        cb.for_each_slice_mut(|s| lens.push(s.len()));
+        assert_eq!(lens, vec![3]);
+        lens.clear();
+        cb.extend(&[4,5,6]);
+        cb.for_each_slice_mut(|s| {
+            lens.push(s.len());
+            s.reverse();
+        });
+        assert_eq!(lens, vec![2, 2]);
+        assert!(cb_eq(&cb, &[4,3,6,5]));
+    }
+
+    #[test]
+    fn test_filled(){
+        let cb = CircularBuffer::filled(3, 7u8);
+        assert_eq!(cb.capacity(), 3);
+        assert!(cb_eq(&cb, &[7,7,7]));
+        let cb: CircularBuffer<u8> = CircularBuffer::filled(0, 7);
+        assert!(cb.is_empty());
+        let mut cb = CircularBuffer::from_fn(3, |i| i as i32 - 1);
+        assert!(cb.is_full());
+        cb.push_back(5);
+        assert!(cb_eq(&cb, &[0,1,5]));
+    }
+
+    #[test]
+    fn test_try_resize(){
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(&[1,2,3,4]);
+        cb.try_resize(2).unwrap();
+        assert!(cb_eq(&cb, &[3,4]));
+        assert!(cb.try_resize(usize::MAX).is_err());
+        assert!(cb_eq(&cb, &[3,4]));
+        assert_eq!(cb.capacity(), 2);
+        let cb: CircularBuffer<u8> = CircularBuffer::try_with_capacity(0).unwrap();
+        assert_eq!(cb.capacity(), 0);
+    }
+
+    #[test]
+    fn test_chunks_exact(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(1..10);
+        //content is split as [5], [6,7,8,9]
+        let chunks = cb.chunks_exact(3);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks.remainder_len(), 2);
+        let v: Vec<(&[i32], &[i32])> = chunks.collect();
+        assert_eq!(v, vec![(&[5][..], &[6,7][..])]);
+        assert_eq!(cb.chunks_exact(5).count(), 1);
+        assert_eq!(cb.chunks_exact(6).remainder_len(), 5);
+        let empty: CircularBuffer<i32> = CircularBuffer::new(2);
+        assert_eq!(empty.chunks_exact(1).count(), 0);
+    }
+
+    #[test]
+    fn test_windows(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(1..10);
+        //content is split as [5], [6,7,8,9]
+        let windows: Vec<(&[i32], &[i32])> = cb.windows(3).collect();
+        assert_eq!(windows, vec![
+            (&[5][..], &[6,7][..]),
+            (&[6,7,8][..], &[][..]),
+            (&[7,8,9][..], &[][..])
+        ]);
+        assert_eq!(cb.windows(2).len(), 4);
+        assert_eq!(cb.windows(2).next_back(), Some((&[8,9][..], &[][..])));
+        assert_eq!(cb.windows(5).count(), 1);
+        assert_eq!(cb.windows(6).count(), 0);
+        assert_eq!(cb.chunks(2).count(), 3);
+        let empty: CircularBuffer<i32> = CircularBuffer::new(2);
+        assert_eq!(empty.windows(1).next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windows_zero(){
+        let cb = CircularBuffer::from(vec![1,2,3]);
+        cb.windows(0);
+    }
+
+    #[test]
+    fn test_make_contiguous(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(0..3);
+        cb.pop_front();
+        //already continuous content is not moved
+        let ptr = cb.slices().0.as_ptr();
+        assert_eq!(cb.make_contiguous(), &[1,2]);
+        assert_eq!(cb.slices().0.as_ptr(), ptr);
+        cb.extend(3..6);
+        assert!(!cb.is_contiguous());
+        assert_eq!(cb.as_single_slice(), None);
+        assert_eq!(cb.make_contiguous(), &[2,3,4,5]);
+        assert_eq!(cb.as_single_slice(), Some(&[2,3,4,5][..]));
+        let mut empty: CircularBuffer<i32> = CircularBuffer::new(0);
+        assert!(empty.is_contiguous());
+        assert!(empty.make_contiguous().is_empty());
+    }
+
+    #[test]
+    fn test_contiguous_up_to_last_slot(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(0..4);
+        cb.pop_front();
+        assert_eq!(cb.slices(), (&[1,2,3][..], &[][..]));
+        assert!(cb.is_contiguous());
+        assert_eq!(cb.as_single_slice(), Some(&[1,2,3][..]));
+        //already continuous content is not moved
+        let ptr = cb.slices().0.as_ptr();
+        assert_eq!(cb.make_contiguous(), &[1,2,3]);
+        assert_eq!(cb.slices().0.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_resize_with_policy(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(0..6);
+        cb.resize_with_policy(3, ResizePolicy::KeepOldest);
+        assert!(cb_eq(&cb, &[2,3,4]));
+        assert_eq!(cb.front_seq(), 2);
+        cb.resize_with_policy(2, ResizePolicy::KeepNewest);
+        assert!(cb_eq(&cb, &[3,4]));
+        assert_eq!(cb.front_seq(), 3);
+        cb.resize_with_policy(5, ResizePolicy::KeepOldest);
+        assert!(cb_eq(&cb, &[3,4]));
+        assert_eq!(cb.capacity(), 5);
+        cb.resize_with_policy(0, ResizePolicy::KeepOldest);
+        assert!(cb.is_empty());
+    }
+
+    #[test]
+    fn test_shrink_to_fit(){
+        let mut cb = CircularBuffer::new(10);
+        cb.extend(0..13);
+        cb.pop_front();
+        cb.pop_back();
+        assert!(!cb.is_contiguous());
+        let seq = cb.front_seq();
+        cb.shrink_to_fit();
+        assert_eq!(cb.capacity(), 8);
+        assert!(cb.is_contiguous());
+        assert!(cb_eq(&cb, &[4,5,6,7,8,9,10,11]));
+        assert_eq!(cb.front_seq(), seq);
+        assert_eq!(cb.total_popped(), 2);
+        cb.push_back(12);
+        assert!(cb_eq(&cb, &[5,6,7,8,9,10,11,12]));
+        cb.clear();
+        cb.shrink_to_fit();
+        assert_eq!(cb.capacity(), 0);
+
+        let mut lazy: CircularBuffer<i32> = CircularBuffer::default();
+        lazy.set_capacity(5);
+        lazy.shrink_to_fit();
+        assert_eq!(lazy.capacity(), 0);
+    }
+
+    #[test]
+    fn test_spare_capacity(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend((0..4).map(|i| i.to_string()));
+        cb.pop_front();
+        cb.pop_front();
+        {
+            let (a, b) = cb.spare_capacity_mut();
+            assert_eq!((a.len(), b.len()), (1, 2));
+            a[0].write("4".to_string());
+            b[0].write("5".to_string());
+            b[1].write("6".to_string());
+        }
+        unsafe { cb.commit(3) };
+        assert!(cb.is_full());
+        assert!(cb.iter().map(|s| s.as_str()).eq(vec!["2", "3", "4", "5", "6"]));
+        assert_eq!(cb.total_pushed(), 7);
+        let (a, b) = cb.spare_capacity_mut();
+        assert!(a.is_empty() && b.is_empty());
+        unsafe { cb.commit(0) };
+
+        let mut lazy: CircularBuffer<u8> = CircularBuffer::default();
+        lazy.set_capacity(3);
+        assert_eq!(lazy.spare_capacity_mut().0.len(), 3);
+        lazy.spare_capacity_mut().0[0].write(7);
+        unsafe { lazy.commit(1) };
+        assert_eq!(lazy, [7].as_ref());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_over_capacity(){
+        let mut cb: CircularBuffer<u8> = CircularBuffer::new(2);
+        cb.push_back(1);
+        unsafe { cb.commit(2) };
+    }
+
+    #[test]
+    fn test_try_push(){
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(&[1,2]);
+        assert_eq!(cb.try_push_front(0), Ok(()));
+        assert_eq!(cb.try_push_back(3), Err(3));
+        assert_eq!(cb.try_push_front(4), Err(4));
+        assert!(cb_eq(&cb, &[0,1,2]));
+        cb.pop_front();
+        assert_eq!(cb.try_push_back(3), Ok(()));
+        assert!(cb_eq(&cb, &[1,2,3]));
+        let mut zero: CircularBuffer<i32> = CircularBuffer::new(0);
+        assert_eq!(zero.try_push_back(1), Err(1));
+        assert_eq!(zero.try_push_front(1), Err(1));
+    }
+
+    #[test]
+    fn test_get(){
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(&[1,2,3,4]);
+        assert_eq!(cb.get(0), Some(&2));
+        assert_eq!(cb.get(2), Some(&4));
+        assert_eq!(cb.get(3), None);
+        *cb.get_mut(1).unwrap() = 7;
+        assert!(cb.get_mut(3).is_none());
+        assert!(cb_eq(&cb, &[2,7,4]));
+        let empty: CircularBuffer<i32> = CircularBuffer::new(0);
+        assert_eq!(empty.get(0), None);
+    }
+
+    #[test]
+    fn test_extend_report(){
+        let mut cb = CircularBuffer::new(3);
+        assert_eq!(cb.extend_report(vec![1,2]), ExtendReport{pushed: 2, evicted: 0});
+        assert_eq!(cb.extend_report(vec![3,4,5,6,7]), ExtendReport{pushed: 5, evicted: 4});
+        assert!(cb_eq(&cb, &[5,6,7]));
+        let mut zero = CircularBuffer::new(0);
+        assert_eq!(zero.extend_report(vec![1,2]), ExtendReport::default());
+        let mut strict = CircularBuffer::with_policy(2, EvictionPolicy::Reject);
+        assert_eq!(strict.extend_report(vec![1,2,3]), ExtendReport{pushed: 2, evicted: 0});
+        assert!(cb_eq(&strict, &[1,2]));
+    }
+
+    #[test]
+    fn test_default(){
+        let mut cb: CircularBuffer<i32> = CircularBuffer::default();
+        assert_eq!(cb.capacity(), 0);
+        cb.push_back(1);
+        assert!(cb.is_empty());
+        cb.resize(1);
+        cb.push_back(1);
+        assert!(cb_eq(&cb, &[1]));
+    }
+
+    #[test]
+    fn test_drain_back(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(&[1,2,3,4,5,6]);
+        {
+            let mut drain = cb.drain();
+            assert_eq!(drain.next(), Some(3));
+            assert_eq!(drain.next_back(), Some(6));
+        }
+        assert!(cb_eq(&cb, &[4,5]));
+        assert_eq!(cb.drain_back().collect::<Vec<_>>(), vec![5,4]);
+        assert!(cb.is_empty());
+    }
+
+    #[test]
+    fn test_owning_iterators(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(&[1,2,3,4,5]);
+        let mut it = cb.clone().into_iter();
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.next_back(), Some(5));
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.rev().collect::<Vec<_>>(), vec![4,3]);
+        let mut drain = cb.drain();
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.by_ref().rev().take(3).count(), 3);
+        assert_eq!(drain.len(), 1);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next(), None);
+        assert_eq!(drain.next(), None);
+    }
+
+    #[test]
+    fn test_iter_wrapped(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..8);
+        assert_ne!(cb.slices().1.len(), 0);
+        let it = cb.iter();
+        assert_eq!(it.len(), 5);
+        assert_eq!(it.clone().count(), 5);
+        assert_eq!(it.clone().last(), Some(&7));
+        assert_eq!(it.clone().rev().cloned().collect::<Vec<_>>(), vec![7,6,5,4,3]);
+        assert_eq!(it.clone().fold(0, |a, b| a * 10 + b), 34567);
+        assert_eq!(it.clone().rfold(0, |a, b| a * 10 + b), 76543);
+        for n in 0..6 {
+            assert_eq!(cb.iter().nth(n), (3..8).nth(n).as_ref());
+            assert_eq!(cb.iter().nth_back(n), (3..8).nth_back(n).as_ref());
+        }
+        let mut it = cb.iter();
+        assert_eq!(it.nth(3), Some(&6));
+        assert_eq!(it.next(), Some(&7));
+        assert_eq!(it.next(), None);
+        for x in cb.iter_mut().skip(1).step_by(2) {
+            *x = 0;
+        }
+        assert!(cb_eq(&cb, &[3,0,5,0,7]));
+        assert_eq!(cb.iter_mut().nth_back(4).map(|x| *x), Some(3));
+    }
+
+    #[test]
+    fn test_vec_deque_conversions(){
+        let rc = Rc::new(());
+        let mut cb = CircularBuffer::new(4);
+        for _ in 0..6 {
+            cb.push_back(rc.clone());
+        }
+        let deque = VecDeque::from(cb);
+        assert_eq!(deque.len(), 4);
+        assert_eq!(Rc::strong_count(&rc), 5);
+        drop(deque);
+        assert_eq!(Rc::strong_count(&rc), 1);
+
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(0..5);
+        let mut deque = VecDeque::from(cb);
+        deque.push_front(1);
+        assert_eq!(deque, vec![1,2,3,4]);
+        let cb = CircularBuffer::from(deque);
+        assert_eq!(cb.capacity(), 4);
+        assert!(cb_eq(&cb, &[1,2,3,4]));
+        let empty: VecDeque<i32> = VecDeque::from(CircularBuffer::new(0));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_rotate(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..7);
+        let seq = (cb.front_seq(), cb.next_seq());
+        for n in 0..=5 {
+            let mut left = cb.clone();
+            left.rotate_left(n);
+            let mut right = cb.clone();
+            right.rotate_right(n);
+            let mut expected: Vec<i32> = (2..7).collect();
+            expected.rotate_left(n);
+            assert!(cb_eq(&left, &expected));
+            expected.rotate_right(n);
+            expected.rotate_right(n);
+            assert!(cb_eq(&right, &expected));
+            assert_eq!((left.front_seq(), left.next_seq()), seq);
+            assert_eq!((right.front_seq(), right.next_seq()), seq);
+        }
+        let mut not_full = CircularBuffer::new(5);
+        not_full.extend(&[1,2,3]);
+        not_full.rotate_left(2);
+        not_full.push_back(4);
+        assert!(cb_eq(&not_full, &[3,1,2,4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_too_far(){
+        let mut cb = CircularBuffer::from(vec![1,2]);
+        cb.rotate_right(3);
+    }
+
+    #[test]
+    fn test_search(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend((0..8).map(|x| x * 2));
+        assert_ne!(cb.slices().1.len(), 0);
+        let sorted: Vec<i32> = cb.iter().cloned().collect();
+        for x in -1..16 {
+            assert_eq!(cb.binary_search(&x), sorted.binary_search(&x));
+            assert_eq!(cb.contains(&x), sorted.contains(&x));
+        }
+        assert_eq!(cb.binary_search_by_key(&5, |x| x / 2), Ok(2));
+        let empty: CircularBuffer<i32> = CircularBuffer::new(3);
+        assert_eq!(empty.binary_search(&1), Err(0));
+        assert!(!empty.contains(&1));
+    }
+
+    #[test]
+    fn test_recycle(){
+        let counter = Rc::new(RefCell::new(0));
+        let mut cb = CircularBuffer::new(4);
+        for _ in 0..6 {
+            cb.push_back(Droppable{counter: counter.clone()});
+        }
+        cb.recycle(2);
+        assert_eq!(*counter.borrow(), 6);
+        assert_eq!(cb.capacity(), 2);
+        cb.push_back(Droppable{counter: counter.clone()});
+        cb.recycle(5);
+        assert_eq!(*counter.borrow(), 7);
+        assert_eq!(cb.capacity(), 5);
+        assert!(cb.is_empty());
+    }
+
+    #[test]
+    fn test_hash_and_eq(){
+        use std::collections::HashSet;
+        let mut wrapped = CircularBuffer::new(3);
+        wrapped.extend(0..5);
+        let linear = CircularBuffer::from(vec![2,3,4]);
+        assert_ne!(wrapped.slices(), linear.slices());
+        let mut set = HashSet::new();
+        set.insert(wrapped.clone());
+        assert!(set.contains(&linear));
+        assert_eq!(wrapped, vec![2,3,4]);
+        assert_eq!(wrapped, [2,3,4]);
+        assert_eq!(wrapped, VecDeque::from(vec![2,3,4]));
+        assert_ne!(wrapped, vec![2,3]);
+    }
+
+    #[test]
+    fn test_zero_sized(){
+        use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        #[derive(Clone, Debug, PartialEq)]
+        struct Marker;
+        impl Drop for Marker {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, AtomicOrdering::SeqCst);
+            }
+        }
+
+        let mut cb = CircularBuffer::new(3);
+        for _ in 0..5 {
+            cb.push_back(Marker);
+        }
+        assert_eq!(DROPS.load(AtomicOrdering::SeqCst), 2);
+        assert_eq!(cb.len(), 3);
+        assert!(cb.is_full());
+        assert_eq!(cb.iter().count(), 3);
+        assert_eq!(cb.iter().rev().count(), 3);
+        let (a, b) = cb.slices();
+        assert_eq!(a.len() + b.len(), 3);
+        assert!(cb.pop_front().is_some());
+        assert_eq!(DROPS.load(AtomicOrdering::SeqCst), 3);
+        cb.push_front(Marker);
+        cb.resize(5);
+        assert_eq!(cb.len(), 3);
+        cb.extend(vec![Marker, Marker, Marker]);
+        assert_eq!(cb.len(), 5);
+        assert_eq!(cb.drain().count(), 5);
+        cb.push_back(Marker);
+        let before = DROPS.load(AtomicOrdering::SeqCst);
+        drop(cb);
+        assert_eq!(DROPS.load(AtomicOrdering::SeqCst), before + 1);
+
+        let mut units: CircularBuffer<()> = CircularBuffer::new(2);
+        units.extend(vec![(); 10]);
+        assert_eq!(units.len(), 2);
+        assert_eq!(units.capacity(), 2);
+        assert_eq!(units, [(), ()]);
+        assert_eq!(units.into_iter().count(), 2);
+        let zero: CircularBuffer<()> = CircularBuffer::new(0);
+        assert_eq!(zero.capacity(), 0);
+        //no memory is needed, so creating huge buffers is cheap
+        let mut huge: CircularBuffer<()> = CircularBuffer::new(usize::MAX / 4);
+        huge.push_back(());
+        huge.resize(usize::MAX / 2);
+        assert_eq!(huge.len(), 1);
+        assert_eq!(huge.capacity(), usize::MAX / 2);
+        assert!(huge.try_resize(usize::MAX).is_ok());
+        assert_eq!(huge.capacity(), usize::MAX);
+        huge.extend(vec![(); 3]);
+        assert_eq!(huge.len(), 4);
+        assert!(CircularBuffer::<()>::try_with_capacity(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_padded_view(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..8);
+        assert_ne!(cb.slices().1.len(), 0);
+        for len in 0..9 {
+            let expected: Vec<i32> = (3..8).chain(core::iter::repeat(-1)).take(len).collect();
+            let view = cb.padded_view(len, -1);
+            assert_eq!(view.len(), len);
+            assert_eq!(view.padding_len(), len.saturating_sub(5));
+            assert_eq!(view.clone().collect::<Vec<_>>(), expected);
+            let mut rev: Vec<i32> = view.rev().collect();
+            rev.reverse();
+            assert_eq!(rev, expected);
+        }
+        let empty: CircularBuffer<i32> = CircularBuffer::new(2);
+        assert_eq!(empty.padded_view(3, 7).collect::<Vec<_>>(), vec![7, 7, 7]);
+    }
+
+    #[test]
+    fn test_debug(){
+        let mut cb = CircularBuffer::new(3);
+        assert_eq!(format!("{:?}", cb), "[]");
+        cb.extend(["a", "b", "c", "d"].iter().map(|s| s.to_string()));
+        assert_eq!(format!("{:?}", cb), format!("{:?}", cb.iter().collect::<VecDeque<_>>()));
+        assert_eq!(format!("{:#?}", cb), format!("{:#?}", vec!["b", "c", "d"]));
+        assert_eq!(cb.internal_state(), "CircularBuffer{ start: 1, len: 3, capacity: 3 }");
+    }
+
+    #[test]
+    fn test_read_into(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..8);
+        assert_ne!(cb.slices().1.len(), 0);
+        let seq = cb.front_seq();
+        let mut dst = [0; 4];
+        assert_eq!(cb.read_into(&mut dst[..0]), 0);
+        assert_eq!(cb.read_into(&mut dst), 4);
+        assert_eq!(dst, [3, 4, 5, 6]);
+        assert_eq!(cb.front_seq(), seq + 4);
+        cb.extend(8..11);
+        assert!(cb_eq(&cb, &[7, 8, 9, 10]));
+        assert_eq!(cb.read_into(&mut dst[..2]), 2);
+        assert_eq!(&dst[..2], &[7, 8]);
+        assert_eq!(cb.read_into(&mut dst), 2);
+        assert_eq!(&dst[..2], &[9, 10]);
+        assert!(cb.is_empty());
+        assert_eq!(cb.read_into(&mut dst), 0);
+        assert_eq!(cb.next_seq(), seq + 8);
+    }
+
+    #[test]
+    fn test_copy_to_slice(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..8);
+        let mut dst = [0; 4];
+        cb.copy_to_slice(&mut dst);
+        assert_eq!(dst, [3, 4, 5, 6]);
+        assert!(cb_eq(&cb, &[7]));
+        cb.copy_to_slice(&mut dst[..0]);
+        cb.copy_to_slice(&mut dst[..1]);
+        assert_eq!(dst[0], 7);
+        assert!(cb.is_empty());
+        assert_eq!(cb.total_popped(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_copy_to_slice_too_long(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..3);
+        cb.copy_to_slice(&mut [0; 4]);
+    }
+
+    #[test]
+    fn test_totals(){
+        let mut cb = CircularBuffer::new(3);
+        cb.extend(0..5);
+        cb.push_front(10);
+        assert_eq!((cb.total_pushed(), cb.total_evicted(), cb.total_popped()), (6, 3, 0));
+        cb.pop_back();
+        cb.drain().next();
+        let mut dst = [0; 1];
+        cb.read_into(&mut dst);
+        assert_eq!((cb.total_pushed(), cb.total_evicted(), cb.total_popped()), (6, 3, 3));
+
+        //internal moves of elements are not counted
+        cb.extend(0..3);
+        cb.rotate_left(1);
+        cb.dedup();
+        cb.resize(5);
+        cb.resize_with_policy(2, ResizePolicy::KeepOldest);
+        cb.clear();
+        assert_eq!((cb.total_pushed(), cb.total_evicted(), cb.total_popped()), (9, 3, 3));
+        cb.resize_len_with(2, || 7);
+        assert_eq!(cb.total_pushed(), 11);
+
+        let mut rejecting = CircularBuffer::with_policy(1, EvictionPolicy::Reject);
+        rejecting.push_back(1);
+        rejecting.push_back(2);
+        assert_eq!(rejecting.try_push_front(3), Err(3));
+        assert_eq!((rejecting.total_pushed(), rejecting.total_evicted()), (1, 0));
+        assert_eq!(rejecting.clone().total_pushed(), 1);
+        rejecting.recycle(1);
+        assert_eq!(rejecting.total_pushed(), 0);
+    }
+
+    #[test]
+    fn test_latest(){
+        let mut cb = CircularBuffer::new(5);
+        assert_eq!(cb.latest(2).count(), 0);
+        cb.extend(0..8);
+        assert_ne!(cb.slices().1.len(), 0);
+        for n in 0..7 {
+            let expected: Vec<i32> = (3..8).skip(5usize.saturating_sub(n)).collect();
+            assert_eq!(cb.latest(n).cloned().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_copy_newest_to_slice(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(0..8);
+        assert_ne!(cb.slices().1.len(), 0);
+        for len in 0..7 {
+            let mut dst = vec![-1; len];
+            let n = cb.copy_newest_to_slice(&mut dst);
+            assert_eq!(n, len.min(5));
+            let expected: Vec<i32> = (3..8).skip(5 - n).collect();
+            assert_eq!(&dst[..n], &expected[..]);
+            assert!(dst[n..].iter().all(|&x| x == -1));
+        }
+    }
+
+    #[test]
+    fn test_fill_and_resize_len(){
+        let mut cb = CircularBuffer::new(4);
+        cb.extend(0..7);
+        cb.pop_back();
+        assert_ne!(cb.slices().1.len(), 0);
+        cb.fill(7);
+        assert!(cb_eq(&cb, &[7,7,7]));
+        cb.resize_len_with(4, || 1);
+        assert!(cb_eq(&cb, &[7,7,7,1]));
+        cb.resize_len_with(0, || 1);
+        assert!(cb.is_empty());
+        let mut empty: CircularBuffer<i32> = CircularBuffer::new(0);
+        empty.fill(1);
+        empty.resize_len_with(0, || 1);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_resize_len_over_capacity(){
+        let mut cb: CircularBuffer<i32> = CircularBuffer::new(2);
+        cb.resize_len_with(3, || 0);
+    }
+
+    #[test]
+    fn test_growth(){
+        let mut cb = CircularBuffer::with_growth(0, 3);
+        cb.push_back(1);
+        assert_eq!(cb.capacity(), 1);
+        cb.push_front(0);
+        assert_eq!(cb.capacity(), 2);
+        assert_eq!(cb.try_push_back(2), Ok(()));
+        assert_eq!(cb.capacity(), 3);
+        assert_eq!(cb.try_push_back(3), Err(3));
+        cb.push_back(3);
+        assert!(cb_eq(&cb, &[1,2,3]));
+        cb.set_max_capacity(4);
+        let report = cb.extend_report(4..6);
+        assert_eq!(report, ExtendReport{pushed: 2, evicted: 1});
+        assert!(cb_eq(&cb, &[2,3,4,5]));
+        assert_eq!(cb.max_capacity(), 4);
+        let mut strict = CircularBuffer::with_growth(1, 2);
+        strict.set_policy(EvictionPolicy::Reject);
+        strict.extend(1..5);
+        assert!(cb_eq(&strict, &[1,2]));
+    }
+
+    #[test]
+    fn test_dedup(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(vec![7, 1, 1, 2, 2, 2, 2]);
+        assert_ne!(cb.slices().1.len(), 0);
+        let next_seq = cb.next_seq();
+        cb.dedup();
+        assert!(cb_eq(&cb, &[1, 2]));
+        assert_eq!(cb.next_seq(), next_seq);
+        cb.extend(vec![2, 3, 3]);
+        cb.dedup();
+        assert!(cb_eq(&cb, &[1, 2, 3]));
+        let mut single = CircularBuffer::from(vec![1]);
+        single.dedup();
+        assert!(cb_eq(&single, &[1]));
+        let mut strings = CircularBuffer::from(vec!["a".to_string(), "A".to_string(), "b".to_string()]);
+        strings.dedup_by_key(|s| s.to_lowercase());
+        assert_eq!(strings, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_on_evict(){
+        use std::sync::{Arc, Mutex};
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        let mut cb = CircularBuffer::new(2);
+        cb.set_on_evict(move |x| sink.lock().unwrap().push(x));
+        cb.extend(1..4);
+        cb.push_front(0);
+        cb.pop_front();
+        cb.resize(0);
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 3]);
+        let mut copy = cb.clone();
+        copy.resize(1);
+        copy.push_back(7);
+        copy.push_back(8);
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 3]);
+        cb.resize(1);
+        cb.remove_on_evict();
+        cb.extend(4..6);
+        assert_eq!(*evicted.lock().unwrap(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_lazy_allocation(){
+        let mut cb: CircularBuffer<i32> = CircularBuffer::default();
+        assert_eq!(cb.ring.capacity(), 0);
+        cb.push_back(1);
+        assert!(cb.is_empty());
+        assert_eq!(cb.ring.capacity(), 0);
+        cb.set_capacity(2);
+        assert_eq!(cb.capacity(), 2);
+        assert!(!cb.is_full());
+        assert_eq!(cb.ring.capacity(), 0);
+        assert_eq!(cb.pop_front(), None);
+        assert!(cb.iter().next().is_none());
+        cb.push_front(1);
+        assert_eq!(cb.ring.capacity(), 2);
+        cb.set_capacity(1);
+        assert!(cb_eq(&cb, &[1]));
+
+        let mut filled: CircularBuffer<i32> = CircularBuffer::default();
+        filled.set_capacity(3);
+        filled.resize_len_with(2, || 5);
+        assert!(cb_eq(&filled, &[5, 5]));
+        filled.recycle(1);
+        assert_eq!(filled.capacity(), 1);
+        let mut unused: CircularBuffer<i32> = CircularBuffer::default();
+        unused.set_capacity(4);
+        unused.recycle(2);
+        assert_eq!(unused.capacity(), 2);
+        assert_eq!(unused.ring.capacity(), 0);
+        let mut grown: CircularBuffer<i32> = CircularBuffer::default();
+        grown.set_max_capacity(2);
+        grown.extend(1..4);
+        assert!(cb_eq(&grown, &[2, 3]));
+    }
+
+    #[test]
+    fn test_clone(){
+        let counter = Rc::new(RefCell::new(0));
+        let mut cb = CircularBuffer::with_policy(4, EvictionPolicy::Reject);
+        for _ in 0..2 {
+            cb.push_back(Rc::new(Droppable{counter: counter.clone()}));
+        }
+        let copy = cb.clone();
+        assert_eq!(copy.len(), 2);
+        assert_eq!(copy.capacity(), 4);
+        assert_eq!(copy.policy(), EvictionPolicy::Reject);
+        assert!(Rc::ptr_eq(&copy[0], &cb[0]));
+        drop(copy);
+        assert_eq!(*counter.borrow(), 0);
+
+        let mut target = CircularBuffer::new(4);
+        target.push_back(Rc::new(Droppable{counter: counter.clone()}));
+        let ptr = target.slices().0.as_ptr();
+        target.clone_from(&cb);
+        assert_eq!(target.slices().0.as_ptr(), ptr);
+        assert_eq!(*counter.borrow(), 1);
+        drop(target);
+        drop(cb);
+        assert_eq!(*counter.borrow(), 3);
+
+        let mut ints = CircularBuffer::new(3);
+        ints.extend(0..5);
+        let mut small = CircularBuffer::new(1);
+        small.clone_from(&ints);
+        assert!(cb_eq(&small, &[2, 3, 4]));
+        assert_eq!(small.next_seq(), ints.next_seq());
+        let lazy: CircularBuffer<i32> = CircularBuffer::default();
+        assert_eq!(lazy.clone().ring.capacity(), 0);
+    }
+
+    #[test]
+    fn test_eviction_policy(){
+        let mut cb = CircularBuffer::with_policy(2, EvictionPolicy::Reject);
+        cb.extend(&[1,2,3]);
+        cb.push_front(0);
+        assert!(cb_eq(&cb, &[1,2]));
+        cb.set_policy(EvictionPolicy::Overwrite);
+        cb.push_front(0);
+        assert!(cb_eq(&cb, &[0,1]));
+        let mut zero = CircularBuffer::with_policy(0, EvictionPolicy::Reject);
+        zero.push_back(1);
+        assert!(zero.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_eviction_policy_panic(){
+        let mut cb = CircularBuffer::with_policy(2, EvictionPolicy::Panic);
+        cb.extend(&[1,2]);
+        cb.push_back(3);
+    }
+
+    #[test]
+    fn test_retain_logical(){
+        let mut cb = CircularBuffer::new(5);
+        cb.extend(1..10);
+        assert_eq!(cb.next_seq(), 9);
+        cb.retain_logical(&IntervalSet::from_iter(vec![Interval::single(0), Interval::open(2, 10)]));
+        assert!(cb_eq(&cb, &[5,8,9]));
+        assert_eq!(cb.next_seq(), 9);
+        assert_eq!(cb.front_seq(), 6);
+        cb.retain_logical(&IntervalSet::new());
+        assert!(cb.is_empty());
+        assert_eq!(cb.front_seq(), 9);
+    }
+}
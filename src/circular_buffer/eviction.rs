@@ -0,0 +1,84 @@
+/**
+Defines what happens when an element is pushed into a full `CircularBuffer`.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::{CircularBuffer, EvictionPolicy};
+
+fn main(){
+    //the default policy removes the oldest element
+    let mut lossy = CircularBuffer::new(2);
+    lossy.extend(&[1, 2, 3]);
+    assert_eq!(lossy, [2, 3].as_ref());
+
+    //a strict bounded buffer ignores elements that do not fit
+    let mut strict = CircularBuffer::with_policy(2, EvictionPolicy::Reject);
+    strict.extend(&[1, 2, 3]);
+    assert_eq!(strict, [1, 2].as_ref());
+}
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum EvictionPolicy {
+    ///Removes an element from the opposite end of the buffer to make space for the new one.
+    #[default]
+    Overwrite,
+    ///Drops the new element, leaving the buffer unchanged.
+    Reject,
+    ///Panics.
+    Panic
+}
+
+/**
+Defines which elements are kept when a `CircularBuffer` shrinks below its length.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::{CircularBuffer, ResizePolicy};
+
+fn main(){
+    let mut recent = CircularBuffer::from(vec![1, 2, 3, 4]);
+    recent.resize_with_policy(2, ResizePolicy::KeepNewest);
+    assert_eq!(recent, [3, 4].as_ref());
+
+    let mut replay = CircularBuffer::from(vec![1, 2, 3, 4]);
+    replay.resize_with_policy(2, ResizePolicy::KeepOldest);
+    assert_eq!(replay, [1, 2].as_ref());
+}
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ResizePolicy {
+    ///Removes elements from the front of the buffer.
+    #[default]
+    KeepNewest,
+    ///Removes elements from the back of the buffer.
+    KeepOldest
+}
+
+/**
+Summary of an `extend_report()` call on `CircularBuffer`.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::{CircularBuffer, ExtendReport};
+
+fn main(){
+    let mut cb = CircularBuffer::new(3);
+    cb.push_back(0);
+    let report = cb.extend_report(1..5);
+    assert_eq!(report, ExtendReport{pushed: 4, evicted: 2});
+    assert_eq!(cb, [2, 3, 4].as_ref());
+}
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct ExtendReport {
+    ///Number of elements appended to the buffer.
+    pub pushed: usize,
+    ///Number of elements removed from the front of the buffer to make space for new ones.
+    pub evicted: usize
+}
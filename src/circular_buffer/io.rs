@@ -0,0 +1,156 @@
+use std::io::{self, Read, Write, BufRead, IoSliceMut};
+use super::circular::CircularBuffer;
+
+/*
+`CircularBuffer<u8>` works as a bounded byte FIFO: writing appends bytes at the back
+and reading removes them from the front.
+Writes never overwrite unread data, regardless of the eviction policy -
+only the free space is filled and the number of written bytes is returned.
+*/
+
+impl Read for CircularBuffer<u8> {
+    /**
+    Moves bytes from the front of the buffer into the provided slice.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::io::{Read, Write};
+
+    fn main(){
+        let mut fifo: CircularBuffer<u8> = CircularBuffer::new(8);
+        fifo.write_all(b"hello").unwrap();
+        let mut out = [0u8; 3];
+        assert_eq!(fifo.read(&mut out).unwrap(), 3);
+        assert_eq!(&out, b"hel");
+        assert_eq!(fifo, b"lo".as_ref());
+    }
+    ```
+    */
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = copy_front(self, buf);
+        self.consume(n);
+        Ok(n)
+    }
+
+    ///Fills the provided slices in order, using both internal slices of the buffer.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs.iter_mut() {
+            let n = copy_front(self, buf);
+            self.consume(n);
+            total += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+}
+
+impl BufRead for CircularBuffer<u8> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        for _ in 0..amt.min(self.len()) {
+            self.pop_front();
+        }
+    }
+}
+
+impl Write for CircularBuffer<u8> {
+    /**
+    Appends as many bytes as fit into the free space of the buffer.
+
+    Returns 0 if the buffer is full, so `write_all()` fails with `ErrorKind::WriteZero`
+    instead of overwriting unread data.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::CircularBuffer;
+    use std::io::Write;
+
+    fn main(){
+        let mut fifo: CircularBuffer<u8> = CircularBuffer::new(4);
+        assert_eq!(fifo.write(b"abcdef").unwrap(), 4);
+        assert_eq!(fifo.write(b"gh").unwrap(), 0);
+        assert_eq!(fifo, b"abcd".as_ref());
+    }
+    ```
+    */
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.capacity() - self.len());
+        self.extend(buf[..n].iter().cloned());
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+//copies bytes from the front of the buffer without removing them
+fn copy_front(cb: &CircularBuffer<u8>, buf: &mut [u8]) -> usize {
+    let (a, b) = cb.slices();
+    let n1 = a.len().min(buf.len());
+    buf[..n1].copy_from_slice(&a[..n1]);
+    let n2 = b.len().min(buf.len() - n1);
+    buf[n1..n1 + n2].copy_from_slice(&b[..n2]);
+    n1 + n2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping_fifo(){
+        let mut fifo: CircularBuffer<u8> = CircularBuffer::new(5);
+        let mut out = [0u8; 4];
+        fifo.write_all(b"abc").unwrap();
+        assert_eq!(fifo.read(&mut out[..2]).unwrap(), 2);
+        //the content crosses the end of the internal buffer now
+        fifo.write_all(b"defg").unwrap();
+        assert_eq!(fifo.write(b"x").unwrap(), 0);
+        assert_eq!(fifo.write_all(b"x").unwrap_err().kind(), io::ErrorKind::WriteZero);
+        assert_eq!(fifo.read(&mut out).unwrap(), 4);
+        assert_eq!(&out, b"cdef");
+        assert_eq!(fifo.read(&mut out).unwrap(), 1);
+        assert_eq!(out[0], b'g');
+        assert_eq!(fifo.read(&mut out).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_read_vectored(){
+        let mut fifo: CircularBuffer<u8> = CircularBuffer::new(6);
+        fifo.write_all(b"1234").unwrap();
+        fifo.consume(3);
+        fifo.write_all(b"56789").unwrap();
+        let (mut a, mut b, mut c) = ([0u8; 2], [0u8; 3], [0u8; 4]);
+        let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b), IoSliceMut::new(&mut c)];
+        assert_eq!(fifo.read_vectored(&mut bufs).unwrap(), 6);
+        assert_eq!((&a, &b, &c[..1]), (b"45", b"678", &b"9"[..]));
+        assert!(fifo.is_empty());
+    }
+
+    #[test]
+    fn test_buf_read(){
+        let mut fifo: CircularBuffer<u8> = CircularBuffer::new(8);
+        fifo.write_all(b"ab\ncd").unwrap();
+        fifo.consume(3);
+        fifo.write_all(b"ef\ngh").unwrap();
+        let mut line = String::new();
+        fifo.read_line(&mut line).unwrap();
+        assert_eq!(line, "cdef\n");
+        assert_eq!(fifo, b"gh".as_ref());
+        //only the continuous part of the content is returned
+        assert!(b"gh".starts_with(fifo.fill_buf().unwrap()));
+        assert!(!fifo.fill_buf().unwrap().is_empty());
+        fifo.consume(10);
+        assert!(fifo.is_empty());
+    }
+}
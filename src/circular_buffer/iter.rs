@@ -1,20 +1,144 @@
-use std::iter::{Chain};
-use std::slice::{Iter as SliceIter, IterMut as SliceIterMut};
-use std::iter::Iterator;
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator};
+use core::slice::{Iter as SliceIter, IterMut as SliceIterMut};
+use core::iter::Iterator;
+#[cfg(feature = "std")]
 use super::circular::CircularBuffer;
 
 
+/*
+Iter and IterMut traverse two slices of the buffer, first the one with the front
+of the buffer, then the one with the back. Most operations can be delegated to the
+slice iterators, this macro implements them for both types.
+*/
+macro_rules! impl_two_slice_iter {
+    ($name:ident, $item:ty) => {
+        impl<'a, T> Iterator for $name<'a, T> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+                match self.first.next() {
+                    None => self.second.next(),
+                    item => item
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.first.len() + self.second.len();
+                (len, Some(len))
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.first.len() + self.second.len()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<<Self as Iterator>::Item> {
+                let first_len = self.first.len();
+                if n < first_len {
+                    return self.first.nth(n);
+                }
+                //skips all remaining elements of the first slice
+                self.first.nth(first_len);
+                self.second.nth(n - first_len)
+            }
+
+            fn last(mut self) -> Option<<Self as Iterator>::Item> {
+                match self.second.next_back() {
+                    None => self.first.next_back(),
+                    item => item
+                }
+            }
+
+            fn fold<B, F>(self, init: B, mut f: F) -> B where F: FnMut(B, <Self as Iterator>::Item) -> B {
+                let acc = self.first.fold(init, &mut f);
+                self.second.fold(acc, f)
+            }
+        }
+
+        impl<'a, T> DoubleEndedIterator for $name<'a, T> {
+            #[inline]
+            fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+                match self.second.next_back() {
+                    None => self.first.next_back(),
+                    item => item
+                }
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<<Self as Iterator>::Item> {
+                let second_len = self.second.len();
+                if n < second_len {
+                    return self.second.nth_back(n);
+                }
+                self.second.nth_back(second_len);
+                self.first.nth_back(n - second_len)
+            }
+
+            fn rfold<B, F>(self, init: B, mut f: F) -> B where F: FnMut(B, <Self as Iterator>::Item) -> B {
+                let acc = self.second.rfold(init, &mut f);
+                self.first.rfold(acc, f)
+            }
+        }
+
+        impl<'a, T> ExactSizeIterator for $name<'a, T> {}
+
+        impl<'a, T> FusedIterator for $name<'a, T> {}
+    };
+}
+
 /// An iterator over `CircularBuffer<T>`.
-pub type Iter<'a, T> = Chain<SliceIter<'a, T>, SliceIter<'a, T>>;
+#[derive(Debug)]
+pub struct Iter<'a, T>{
+    first: SliceIter<'a, T>,
+    second: SliceIter<'a, T>
+}
+
+impl<'a, T> Iter<'a, T>{
+    pub fn new(first: &'a [T], second: &'a [T]) -> Self{
+        Self{
+            first: first.iter(),
+            second: second.iter()
+        }
+    }
+}
+
+impl<'a, T> Clone for Iter<'a, T>{
+    fn clone(&self) -> Self {
+        Self{
+            first: self.first.clone(),
+            second: self.second.clone()
+        }
+    }
+}
+
+impl_two_slice_iter!(Iter, &'a T);
 
 /// A mutable iterator over `CircularBuffer<T>`.
-pub type IterMut<'a, T> = Chain<SliceIterMut<'a, T>, SliceIterMut<'a, T>>;
+#[derive(Debug)]
+pub struct IterMut<'a, T>{
+    first: SliceIterMut<'a, T>,
+    second: SliceIterMut<'a, T>
+}
+
+impl<'a, T> IterMut<'a, T>{
+    pub fn new(first: &'a mut [T], second: &'a mut [T]) -> Self{
+        Self{
+            first: first.iter_mut(),
+            second: second.iter_mut()
+        }
+    }
+}
+
+impl_two_slice_iter!(IterMut, &'a mut T);
 
 ///A drainign iterator over `CircularBuffer<T>`.
+#[cfg(feature = "std")]
 pub struct Drain<'a, T>{
     buf: &'a mut CircularBuffer<T>
 }
 
+#[cfg(feature = "std")]
 impl<'a, T> Drain<'a, T>{
     pub fn new(buf: &'a mut CircularBuffer<T>) -> Self{
         Drain{
@@ -23,19 +147,39 @@ impl<'a, T> Drain<'a, T>{
     }
 }
 
+#[cfg(feature = "std")]
 impl <'a, T> Iterator for Drain<'a, T>{
     type Item = T;
 
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
         self.buf.pop_front()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buf.len(), Some(self.buf.len()))
+    }
 }
 
+#[cfg(feature = "std")]
+impl <'a, T> DoubleEndedIterator for Drain<'a, T>{
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        self.buf.pop_back()
+    }
+}
+
+#[cfg(feature = "std")]
+impl <'a, T> ExactSizeIterator for Drain<'a, T>{}
+
+#[cfg(feature = "std")]
+impl <'a, T> FusedIterator for Drain<'a, T>{}
+
 ///An iterator that moves out of a `CircularBuffer<T>`.
+#[cfg(feature = "std")]
 pub struct IntoIter<T>{
     buf: CircularBuffer<T>
 }
 
+#[cfg(feature = "std")]
 impl<T> IntoIter<T>{
     pub fn new(buf: CircularBuffer<T>) -> Self{
         Self{
@@ -44,10 +188,280 @@ impl<T> IntoIter<T>{
     }
 }
 
+#[cfg(feature = "std")]
 impl <T> Iterator for IntoIter<T>{
     type Item = T;
 
     fn next(&mut self) -> Option<<Self as Iterator>::Item> {
         self.buf.pop_front()
     }
-}
\ No newline at end of file
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buf.len(), Some(self.buf.len()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl <T> DoubleEndedIterator for IntoIter<T>{
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        self.buf.pop_back()
+    }
+}
+
+#[cfg(feature = "std")]
+impl <T> ExactSizeIterator for IntoIter<T>{}
+
+#[cfg(feature = "std")]
+impl <T> FusedIterator for IntoIter<T>{}
+
+/**
+An iterator over fixed-size chunks of a `CircularBuffer<T>`.
+
+Each chunk is represented by two slices, because a chunk can span across the end of
+the internal buffer. The second slice is empty if the chunk is continuous in memory.
+The last chunk may be shorter than the requested size.
+*/
+pub struct Chunks<'a, T>{
+    first: &'a [T],
+    second: &'a [T],
+    size: usize
+}
+
+impl<'a, T> Chunks<'a, T>{
+    pub fn new(first: &'a [T], second: &'a [T], size: usize) -> Self{
+        if size == 0 {
+            panic!("Chunk size needs to be greater than zero.");
+        }
+        Self{
+            first,
+            second,
+            size
+        }
+    }
+}
+
+impl <'a, T> Iterator for Chunks<'a, T>{
+    type Item = (&'a [T], &'a [T]);
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.first.is_empty() {
+            if self.second.is_empty() {
+                return None;
+            }
+            self.first = self.second;
+            self.second = &[];
+        }
+        if self.first.len() >= self.size {
+            let (chunk, rest) = self.first.split_at(self.size);
+            self.first = rest;
+            return Some((chunk, &[]));
+        }
+        //the chunk crosses the end of the internal buffer
+        let missing = (self.size - self.first.len()).min(self.second.len());
+        let (tail, rest) = self.second.split_at(missing);
+        let chunk = (self.first, tail);
+        self.first = rest;
+        self.second = &[];
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.first.len() + self.second.len();
+        let chunks = len.div_ceil(self.size);
+        (chunks, Some(chunks))
+    }
+}
+
+/**
+An iterator over complete fixed-size chunks of a `CircularBuffer<T>`.
+
+Chunks are represented the same way as in `Chunks`.
+Elements that do not fill a complete chunk are not yielded,
+their number is available through `remainder_len()`.
+*/
+pub struct ChunksExact<'a, T>{
+    inner: Chunks<'a, T>,
+    remaining: usize,
+    remainder_len: usize
+}
+
+impl<'a, T> ChunksExact<'a, T>{
+    pub fn new(first: &'a [T], second: &'a [T], size: usize) -> Self{
+        let inner = Chunks::new(first, second, size);
+        let len = first.len() + second.len();
+        Self{
+            inner,
+            remaining: len / size,
+            remainder_len: len % size
+        }
+    }
+
+    ///Returns the number of trailing elements that do not form a complete chunk.
+    pub fn remainder_len(&self) -> usize {
+        self.remainder_len
+    }
+}
+
+impl <'a, T> Iterator for ChunksExact<'a, T>{
+    type Item = (&'a [T], &'a [T]);
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl <'a, T> ExactSizeIterator for ChunksExact<'a, T>{}
+
+/**
+An iterator over overlapping windows of a `CircularBuffer<T>`.
+
+Windows are represented the same way as chunks in `Chunks`. Each subsequent window
+starts one element further. No windows are yielded if the buffer contains fewer elements
+than the window size.
+*/
+pub struct Windows<'a, T>{
+    first: &'a [T],
+    second: &'a [T],
+    size: usize,
+    //start of the next window from the front
+    front: usize,
+    //start of the next window from the back, exclusive
+    back: usize
+}
+
+impl<'a, T> Windows<'a, T>{
+    pub fn new(first: &'a [T], second: &'a [T], size: usize) -> Self{
+        if size == 0 {
+            panic!("Window size needs to be greater than zero.");
+        }
+        let len = first.len() + second.len();
+        Self{
+            first,
+            second,
+            size,
+            front: 0,
+            back: (len + 1).saturating_sub(size)
+        }
+    }
+
+    fn window(&self, start: usize) -> (&'a [T], &'a [T]) {
+        let end = start + self.size;
+        let first_len = self.first.len();
+        if end <= first_len {
+            (&self.first[start..end], &[])
+        } else if start >= first_len {
+            (&self.second[start - first_len..end - first_len], &[])
+        } else {
+            (&self.first[start..], &self.second[..end - first_len])
+        }
+    }
+}
+
+impl <'a, T> Iterator for Windows<'a, T>{
+    type Item = (&'a [T], &'a [T]);
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let window = self.window(self.front);
+        self.front += 1;
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back.saturating_sub(self.front);
+        (len, Some(len))
+    }
+}
+
+impl <'a, T> DoubleEndedIterator for Windows<'a, T>{
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.window(self.back))
+    }
+}
+
+impl <'a, T> ExactSizeIterator for Windows<'a, T>{}
+
+impl <'a, T> FusedIterator for Windows<'a, T>{}
+
+/**
+An iterator over elements of a `CircularBuffer<T>` followed by padding.
+
+Elements are cloned from the buffer and followed by clones of the padding value,
+so that exactly the requested number of values is produced.
+*/
+#[derive(Debug, Clone)]
+pub struct PaddedView<'a, T>{
+    iter: Iter<'a, T>,
+    pad: T,
+    //remaining number of padding values
+    pad_len: usize
+}
+
+impl<'a, T> PaddedView<'a, T>{
+    pub fn new(first: &'a [T], second: &'a [T], len: usize, pad: T) -> Self{
+        let (first, second) = if len <= first.len() {
+            (&first[..len], &second[..0])
+        } else {
+            (first, &second[..second.len().min(len - first.len())])
+        };
+        Self{
+            iter: Iter::new(first, second),
+            pad,
+            pad_len: len - first.len() - second.len()
+        }
+    }
+
+    ///Returns the number of padding values that remain to be yielded.
+    pub fn padding_len(&self) -> usize {
+        self.pad_len
+    }
+}
+
+impl <'a, T> Iterator for PaddedView<'a, T> where T: Clone {
+    type Item = T;
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        match self.iter.next() {
+            Some(val) => Some(val.clone()),
+            None if self.pad_len > 0 => {
+                self.pad_len -= 1;
+                Some(self.pad.clone())
+            },
+            None => None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.len() + self.pad_len;
+        (len, Some(len))
+    }
+}
+
+impl <'a, T> DoubleEndedIterator for PaddedView<'a, T> where T: Clone {
+    fn next_back(&mut self) -> Option<<Self as Iterator>::Item> {
+        if self.pad_len > 0 {
+            self.pad_len -= 1;
+            Some(self.pad.clone())
+        } else {
+            self.iter.next_back().cloned()
+        }
+    }
+}
+
+impl <'a, T> ExactSizeIterator for PaddedView<'a, T> where T: Clone {}
+
+impl <'a, T> FusedIterator for PaddedView<'a, T> where T: Clone {}
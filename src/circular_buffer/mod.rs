@@ -8,6 +8,13 @@ It differs from the standard `VecDeque` collection by having a constant buffer s
 removing last items when the buffer gets full. It is also faster thanks to not allocating any
 memory while being used.
 
+`ArrayCircularBuffer` is a variant with a capacity known at compile time. It stores elements
+inline, never allocates and is available without the `std` feature. It is a `GenericCircularBuffer`
+backed by an array - the same implementation can also work on borrowed or boxed memory
+described by the `Storage` trait. It has a smaller API than `CircularBuffer`,
+which is built on a `GenericCircularBuffer` backed by a boxed slice and adds resizing
+and other features that need the heap.
+
 **More:** <https://en.wikipedia.org/wiki/Circular_buffer>
 
 # Complexity
@@ -23,8 +30,46 @@ memory while being used.
 This implementation was inspired by C++ boos library [circular_buffer](https://www.boost.org/doc/libs/1_69_0/doc/html/circular_buffer.html)
 */
 
+#[cfg(feature = "std")]
 mod circular;
+mod array;
+mod storage;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(all(feature = "std", feature = "bytes"))]
+mod bytes;
+#[cfg(feature = "std")]
+mod pool;
+#[cfg(feature = "std")]
+mod rolling;
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(all(feature = "std", feature = "rayon"))]
+mod par_iter;
 mod iter;
+mod slice;
+mod eviction;
+#[cfg(all(feature = "std", any(test, feature = "test-util")))]
+pub mod test_util;
 
+#[cfg(feature = "std")]
 pub use self::circular::CircularBuffer;
-pub use self::iter::{IntoIter, Iter, IterMut, Drain};
\ No newline at end of file
+#[cfg(feature = "std")]
+pub use self::builder::CircularBufferBuilder;
+#[cfg(feature = "std")]
+pub use self::pool::BufferPool;
+#[cfg(feature = "std")]
+pub use self::rolling::RollingStats;
+#[cfg(feature = "std")]
+pub use self::timed::TimedCircularBuffer;
+pub use self::array::{ArrayCircularBuffer, GenericCircularBuffer};
+pub use self::storage::Storage;
+pub use self::eviction::{EvictionPolicy, ExtendReport, ResizePolicy};
+pub use self::slice::{CircularSlice, Layout};
+#[cfg(feature = "std")]
+pub use self::iter::{IntoIter, Drain};
+pub use self::iter::{Iter, IterMut, Chunks, ChunksExact, Windows, PaddedView};
+#[cfg(all(feature = "std", feature = "rayon"))]
+pub use self::par_iter::{ParIter, ParIterMut, ParIntoIter};
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use rayon::iter::{Chain, IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator};
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::collections::vec_deque::IntoIter as VecDequeIntoIter;
+use rayon::slice::{Iter as SliceIter, IterMut as SliceIterMut};
+use super::circular::CircularBuffer;
+
+///A parallel iterator over `CircularBuffer<T>`.
+#[derive(Debug)]
+pub struct ParIter<'a, T> where T: Sync {
+    inner: Chain<SliceIter<'a, T>, SliceIter<'a, T>>
+}
+
+///A parallel mutable iterator over `CircularBuffer<T>`.
+#[derive(Debug)]
+pub struct ParIterMut<'a, T> where T: Send {
+    inner: Chain<SliceIterMut<'a, T>, SliceIterMut<'a, T>>
+}
+
+///A parallel iterator moving elements out of `CircularBuffer<T>`.
+#[derive(Debug)]
+pub struct ParIntoIter<T> where T: Send {
+    inner: VecDequeIntoIter<T>
+}
+
+/*
+The parallel iterators only hide the rayon types that implement them,
+so all operations are delegated to the inner iterator.
+*/
+macro_rules! impl_par_iter {
+    ($name:ty, [$($generics:tt)*], $bound:ident, $item:ty) => {
+        impl<$($generics)*> ParallelIterator for $name where T: $bound {
+            type Item = $item;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result where C: UnindexedConsumer<Self::Item> {
+                self.inner.drive_unindexed(consumer)
+            }
+
+            fn opt_len(&self) -> Option<usize> {
+                self.inner.opt_len()
+            }
+        }
+
+        impl<$($generics)*> IndexedParallelIterator for $name where T: $bound {
+            fn drive<C>(self, consumer: C) -> C::Result where C: Consumer<Self::Item> {
+                self.inner.drive(consumer)
+            }
+
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            fn with_producer<CB>(self, callback: CB) -> CB::Output where CB: ProducerCallback<Self::Item> {
+                self.inner.with_producer(callback)
+            }
+        }
+    }
+}
+
+impl_par_iter!(ParIter<'a, T>, ['a, T], Sync, &'a T);
+impl_par_iter!(ParIterMut<'a, T>, ['a, T], Send, &'a mut T);
+impl_par_iter!(ParIntoIter<T>, [T], Send, T);
+
+/**
+Iterates over references to elements of the buffer in parallel.
+
+Both internal slices are split between worker threads directly, so elements are not copied.
+Indexed operations such as `enumerate()` and `zip()` see elements in the order of the buffer.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use rayon::prelude::*;
+
+fn main(){
+    let mut cb = CircularBuffer::new(1000);
+    cb.extend(0..1500i64);
+    let sum: i64 = cb.par_iter().sum();
+    assert_eq!(sum, (500..1500).sum());
+    let first = cb.par_iter().enumerate().find_first(|(_, &x)| x % 7 == 0);
+    assert_eq!(first, Some((4, &504)));
+}
+```
+*/
+impl<'a, T> IntoParallelIterator for &'a CircularBuffer<T> where T: Sync {
+    type Iter = ParIter<'a, T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (a, b) = self.slices();
+        ParIter {
+            inner: a.par_iter().chain(b.par_iter())
+        }
+    }
+}
+
+/**
+Iterates over mutable references to elements of the buffer in parallel.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use rayon::prelude::*;
+
+fn main(){
+    let mut cb = CircularBuffer::new(3);
+    cb.extend(1..5);
+    cb.par_iter_mut().for_each(|x| *x *= 10);
+    assert_eq!(cb, [20, 30, 40].as_ref());
+}
+```
+*/
+impl<'a, T> IntoParallelIterator for &'a mut CircularBuffer<T> where T: Send {
+    type Iter = ParIterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        let (a, b) = self.slices_mut();
+        ParIterMut {
+            inner: a.par_iter_mut().chain(b.par_iter_mut())
+        }
+    }
+}
+
+/**
+Moves elements out of the buffer in parallel.
+
+Elements are first moved into a `VecDeque` with two memory copies, without cloning them.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+use rayon::prelude::*;
+
+fn main(){
+    let mut cb = CircularBuffer::new(3);
+    cb.extend(vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()]);
+    let joined: Vec<String> = cb.into_par_iter().map(|s| s + "!").collect();
+    assert_eq!(joined, vec!["b!", "c!", "d!"]);
+}
+```
+*/
+impl<T> IntoParallelIterator for CircularBuffer<T> where T: Send {
+    type Iter = ParIntoIter<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIntoIter {
+            inner: VecDeque::from(self).into_par_iter()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_iter(){
+        let mut cb = CircularBuffer::new(1000);
+        cb.extend(0..2500u64);
+        assert_ne!(cb.slices().1.len(), 0);
+        let seq: Vec<u64> = cb.iter().map(|x| x * 2).collect();
+        let par: Vec<u64> = cb.par_iter().map(|x| x * 2).collect();
+        assert_eq!(par, seq);
+        assert_eq!(cb.par_iter().len(), 1000);
+
+        cb.par_iter_mut().enumerate().for_each(|(i, x)| *x = i as u64);
+        assert!(cb.iter().cloned().eq(0..1000));
+
+        let owned: Vec<u64> = cb.into_par_iter().rev().collect();
+        assert!(owned.into_iter().eq((0..1000).rev()));
+
+        let empty: CircularBuffer<u64> = CircularBuffer::default();
+        assert_eq!(empty.par_iter().count(), 0);
+    }
+}
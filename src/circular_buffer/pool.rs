@@ -0,0 +1,142 @@
+use super::circular::CircularBuffer;
+use super::eviction::EvictionPolicy;
+
+/**
+A pool of reusable buffers with the same capacity.
+
+Checking out a buffer reuses a previously returned one if there is any,
+so short-lived windows do not allocate memory for every use.
+The pool is not synchronized, it can be stored in a thread local variable
+to share it within a thread.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::BufferPool;
+use std::cell::RefCell;
+
+thread_local! {
+    static WINDOWS: RefCell<BufferPool<u64>> = RefCell::new(BufferPool::new(16, 4));
+}
+
+fn handle_request(samples: &[u64]) -> u64 {
+    let mut window = WINDOWS.with(|pool| pool.borrow_mut().checkout());
+    window.extend(samples.iter().cloned());
+    let sum = window.iter().sum();
+    WINDOWS.with(|pool| pool.borrow_mut().checkin(window));
+    sum
+}
+
+fn main(){
+    assert_eq!(handle_request(&[1, 2, 3]), 6);
+    assert_eq!(handle_request(&[4]), 4);
+    WINDOWS.with(|pool| assert_eq!(pool.borrow().available(), 1));
+}
+```
+*/
+#[derive(Debug)]
+pub struct BufferPool<T> {
+    buffers: Vec<CircularBuffer<T>>,
+    capacity: usize,
+    max_pooled: usize
+}
+
+impl<T> BufferPool<T> {
+    /**
+    Creates an empty pool of buffers with the given capacity.
+
+    At most `max_pooled` returned buffers are kept for reuse, the rest is dropped.
+    */
+    pub fn new(capacity: usize, max_pooled: usize) -> Self {
+        Self {
+            buffers: Vec::new(),
+            capacity,
+            max_pooled
+        }
+    }
+
+    ///Returns capacity of buffers provided by the pool.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    ///Returns the number of buffers ready for reuse.
+    pub fn available(&self) -> usize {
+        self.buffers.len()
+    }
+
+    ///Returns an empty buffer, reusing a pooled one if possible.
+    pub fn checkout(&mut self) -> CircularBuffer<T> {
+        match self.buffers.pop() {
+            Some(buf) => buf,
+            None => CircularBuffer::new(self.capacity)
+        }
+    }
+
+    /**
+    Returns a buffer to the pool.
+
+    The buffer is recycled to the capacity of the pool, so buffers that were resized
+    or were not checked out from this pool can be returned as well.
+    Its configuration is reset too: the eviction policy becomes `Overwrite`,
+    growing is disabled and the function registered by `set_on_evict()` is dropped,
+    so the next user gets a buffer equivalent to a new one.
+    */
+    pub fn checkin(&mut self, mut buf: CircularBuffer<T>) {
+        if self.buffers.len() < self.max_pooled {
+            buf.recycle(self.capacity);
+            buf.set_policy(EvictionPolicy::Overwrite);
+            buf.set_max_capacity(0);
+            buf.remove_on_evict();
+            self.buffers.push(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_pool(){
+        let mut pool: BufferPool<i32> = BufferPool::new(3, 1);
+        let mut a = pool.checkout();
+        let b = pool.checkout();
+        let ptr = a.slices().0.as_ptr();
+        a.extend(vec![1, 2, 3, 4]);
+        a.set_policy(EvictionPolicy::Reject);
+        pool.checkin(a);
+        pool.checkin(b);
+        assert_eq!(pool.available(), 1);
+        let c = pool.checkout();
+        assert!(c.is_empty());
+        assert_eq!(c.capacity(), 3);
+        //the allocation is reused
+        assert_eq!(c.slices().0.as_ptr(), ptr);
+        let mut resized = CircularBuffer::new(10);
+        resized.push_back(1);
+        pool.checkin(resized);
+        assert_eq!(pool.checkout().capacity(), 3);
+    }
+
+    #[test]
+    fn test_checkin_resets_configuration(){
+        let mut pool: BufferPool<i32> = BufferPool::new(2, 1);
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let sink = evicted.clone();
+        let mut a = pool.checkout();
+        a.set_policy(EvictionPolicy::Reject);
+        a.set_max_capacity(8);
+        a.set_on_evict(move |x| sink.lock().unwrap().push(x));
+        pool.checkin(a);
+        let mut b = pool.checkout();
+        assert_eq!(b.policy(), EvictionPolicy::Overwrite);
+        assert_eq!(b.max_capacity(), 0);
+        b.extend(0..5);
+        assert_eq!(b, [3, 4].as_ref());
+        assert!(evicted.lock().unwrap().is_empty());
+        //the callback and everything it captured were dropped
+        assert_eq!(Arc::strong_count(&evicted), 1);
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+use super::circular::CircularBuffer;
+
+/**
+Statistics of a sliding window of the most recently pushed values.
+
+The window is stored in a `CircularBuffer`. Sum, mean, minimum and maximum
+are updated incrementally when a value is pushed and the oldest one gets evicted,
+so reading them never scans the window. Minimum and maximum are tracked using
+monotonic queues, which gives O(1) amortized complexity of pushing.
+
+The sum is updated by adding new values and subtracting evicted ones.
+For floating point values this may accumulate rounding errors over long streams.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::RollingStats;
+
+fn main(){
+    let mut stats = RollingStats::new(3);
+    for latency in [5, 1, 4, 8, 2].iter() {
+        stats.push(*latency);
+    }
+    //window contains 4, 8, 2
+    assert_eq!(stats.sum(), 14);
+    assert_eq!(stats.min(), Some(2));
+    assert_eq!(stats.max(), Some(8));
+    assert_eq!(stats.mean(), Some(14.0 / 3.0));
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct RollingStats<T> {
+    window: CircularBuffer<T>,
+    sum: T,
+    //sequence numbers and values of the window, increasing values
+    min: VecDeque<(u64, T)>,
+    //sequence numbers and values of the window, decreasing values
+    max: VecDeque<(u64, T)>
+}
+
+impl<T> RollingStats<T> where T: Copy + PartialOrd + Add<Output=T> + Sub<Output=T> + Default {
+    ///Creates empty statistics of a window with the given size.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: CircularBuffer::new(window),
+            sum: T::default(),
+            min: VecDeque::new(),
+            max: VecDeque::new()
+        }
+    }
+
+    /**
+    Pushes a value into the window and returns the evicted one, if any.
+
+    A window of size 0 never stores any values.
+    */
+    pub fn push(&mut self, val: T) -> Option<T> {
+        if self.window.capacity() == 0 {
+            return None;
+        }
+        let evicted = if self.window.is_full() {
+            self.window.pop_front()
+        } else {
+            None
+        };
+        if let Some(old) = evicted {
+            self.sum = self.sum - old;
+        }
+        let seq = self.window.next_seq();
+        self.window.push_back(val);
+        self.sum = self.sum + val;
+
+        let front = self.window.front_seq();
+        while matches!(self.min.back(), Some(&(_, m)) if m >= val) {
+            self.min.pop_back();
+        }
+        self.min.push_back((seq, val));
+        while matches!(self.min.front(), Some(&(s, _)) if (s.wrapping_sub(front) as i64) < 0) {
+            self.min.pop_front();
+        }
+        while matches!(self.max.back(), Some(&(_, m)) if m <= val) {
+            self.max.pop_back();
+        }
+        self.max.push_back((seq, val));
+        while matches!(self.max.front(), Some(&(s, _)) if (s.wrapping_sub(front) as i64) < 0) {
+            self.max.pop_front();
+        }
+        evicted
+    }
+
+    ///Returns the sum of values in the window or the default value if the window is empty.
+    pub fn sum(&self) -> T {
+        self.sum
+    }
+
+    /**
+    Returns the arithmetic mean of values in the window or `None` if the window is empty.
+
+    Use `mean_with()` for types that cannot be converted to `f64` losslessly, such as `i64` or `usize`.
+    */
+    pub fn mean(&self) -> Option<f64> where T: Into<f64> {
+        self.mean_with(Into::into)
+    }
+
+    /**
+    Returns the arithmetic mean of values in the window, converting their sum with the given function.
+
+    # Example
+
+    ```
+    use advanced_collections::circular_buffer::RollingStats;
+
+    fn main(){
+        let mut stats: RollingStats<u64> = RollingStats::new(2);
+        assert_eq!(stats.mean_with(|sum| sum as f64), None);
+        stats.push(3);
+        stats.push(4);
+        assert_eq!(stats.mean_with(|sum| sum as f64), Some(3.5));
+    }
+    ```
+    */
+    pub fn mean_with<F>(&self, to_f64: F) -> Option<f64> where F: FnOnce(T) -> f64 {
+        if self.window.is_empty() {
+            None
+        } else {
+            Some(to_f64(self.sum) / self.window.len() as f64)
+        }
+    }
+
+    ///Returns the smallest value in the window or `None` if the window is empty.
+    pub fn min(&self) -> Option<T> {
+        self.min.front().map(|&(_, val)| val)
+    }
+
+    ///Returns the largest value in the window or `None` if the window is empty.
+    pub fn max(&self) -> Option<T> {
+        self.max.front().map(|&(_, val)| val)
+    }
+
+    ///Removes all values from the window.
+    pub fn clear(&mut self) {
+        self.window.clear();
+        self.sum = T::default();
+        self.min.clear();
+        self.max.clear();
+    }
+}
+
+impl<T> RollingStats<T> {
+    ///Returns the number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    ///Checks if the window is empty.
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    ///Returns the maximal number of values in the window.
+    pub fn window_size(&self) -> usize {
+        self.window.capacity()
+    }
+
+    ///Returns the buffer containing values of the window, from the oldest to the newest.
+    pub fn window(&self) -> &CircularBuffer<T> {
+        &self.window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_matches_scan(){
+        let mut rng = XorShift::new(3);
+        for size in 0..6 {
+            let mut stats = RollingStats::new(size);
+            let mut all: Vec<i64> = Vec::new();
+            for _ in 0..200 {
+                let val = rng.below(50) as i64 - 25;
+                all.push(val);
+                stats.push(val);
+                let window = &all[all.len().saturating_sub(size)..];
+                assert_eq!(stats.len(), window.len());
+                assert_eq!(stats.sum(), window.iter().sum::<i64>());
+                assert_eq!(stats.min(), window.iter().cloned().min());
+                assert_eq!(stats.max(), window.iter().cloned().max());
+                let mean = window.iter().sum::<i64>() as f64 / window.len() as f64;
+                assert_eq!(stats.mean_with(|sum| sum as f64), if window.is_empty() { None } else { Some(mean) });
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_returns_evicted(){
+        let mut stats = RollingStats::new(2);
+        assert_eq!(stats.push(1.5), None);
+        assert_eq!(stats.push(2.5), None);
+        assert_eq!(stats.push(3.0), Some(1.5));
+        assert_eq!(stats.mean(), Some(2.75));
+        stats.clear();
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.sum(), 0.0);
+        assert_eq!(stats.window_size(), 2);
+        assert!(stats.window().is_empty());
+    }
+}
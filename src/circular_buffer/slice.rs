@@ -0,0 +1,319 @@
+use core::fmt;
+use core::ops::{Bound, Index, Range, RangeBounds};
+use super::iter::Iter;
+
+/**
+Logical indexes of elements stored in the two slices of a circular buffer.
+
+Elements of the first slice returned by `slices()` have logical indexes from the `first`
+range and elements of the second slice - from the `second` range. The start of a range
+maps an offset in a slice back to the logical index of the element.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::{CircularBuffer, Layout};
+
+fn main(){
+    let mut cb = CircularBuffer::new(4);
+    cb.extend(1..7);
+    let (_, second) = cb.slices();
+    let layout = cb.layout();
+    assert_eq!(layout, Layout{first: 0..2, second: 2..4});
+    //position of a match found in the second slice
+    let offset = second.iter().position(|&x| x == 6).unwrap();
+    assert_eq!(cb[layout.second.start + offset], 6);
+}
+```
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Layout {
+    ///Logical indexes of elements in the first slice.
+    pub first: Range<usize>,
+    ///Logical indexes of elements in the second slice.
+    pub second: Range<usize>
+}
+
+impl Layout {
+    ///Creates a layout of two consecutive slices with the given lengths.
+    pub fn new(first_len: usize, second_len: usize) -> Self {
+        Self {
+            first: 0..first_len,
+            second: first_len..first_len + second_len
+        }
+    }
+
+    /**
+    Maps a logical index to the number of the slice (0 or 1) and the offset in that slice.
+
+    Returns `None` if the index is out of bounds.
+    */
+    pub fn logical_to_physical(&self, index: usize) -> Option<(usize, usize)> {
+        if self.first.contains(&index) {
+            Some((0, index))
+        } else if self.second.contains(&index) {
+            Some((1, index - self.second.start))
+        } else {
+            None
+        }
+    }
+
+    /**
+    Maps the number of a slice (0 or 1) and an offset in that slice to a logical index.
+
+    Returns `None` if the offset is out of bounds of the slice.
+    */
+    pub fn physical_to_logical(&self, slice: usize, offset: usize) -> Option<usize> {
+        let range = match slice {
+            0 => &self.first,
+            1 => &self.second,
+            _ => return None
+        };
+        if offset < range.len() {
+            Some(range.start + offset)
+        } else {
+            None
+        }
+    }
+}
+
+/**
+A read-only view of a range of elements of a `CircularBuffer<T>`.
+
+The view is represented by two slices, because the range can span across the end
+of the internal buffer. It does not copy elements and does not modify the buffer,
+so it can be obtained from an immutable buffer.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::CircularBuffer;
+
+fn main(){
+    let mut cb = CircularBuffer::new(5);
+    cb.extend(1..9);
+    //content is 4, 5, 6, 7, 8
+    let view = cb.range(1..4);
+    assert_eq!(view.len(), 3);
+    assert_eq!(view[0], 5);
+    assert_eq!(view, [5, 6, 7].as_ref());
+    assert_eq!(view.iter().sum::<i32>(), 18);
+}
+```
+*/
+pub struct CircularSlice<'a, T>{
+    first: &'a [T],
+    second: &'a [T]
+}
+
+impl<'a, T> CircularSlice<'a, T>{
+    /**
+    Creates a view of the given range of elements stored in two consecutive slices.
+
+    Panics if the range is out of bounds or its start is greater than its end.
+    */
+    pub fn new<R>(first: &'a [T], second: &'a [T], range: R) -> Self where R: RangeBounds<usize> {
+        let len = first.len() + second.len();
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.checked_add(1).expect("Range start overflow."),
+            Bound::Unbounded => 0
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1).expect("Range end overflow."),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => len
+        };
+        if start > end {
+            panic!("Range starts at {} but ends at {}.", start, end);
+        }
+        if end > len {
+            panic!("Range end {} is out of bounds for length {}.", end, len);
+        }
+        let first_len = first.len();
+        if end <= first_len {
+            Self{ first: &first[start..end], second: &[] }
+        } else if start >= first_len {
+            Self{ first: &second[start - first_len..end - first_len], second: &[] }
+        } else {
+            Self{ first: &first[start..], second: &second[..end - first_len] }
+        }
+    }
+
+    ///Returns the number of elements in the view.
+    pub fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+
+    ///Checks if the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.first.is_empty() && self.second.is_empty()
+    }
+
+    ///Returns the element at the given position or `None` if the position is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.first.len() {
+            Some(&self.first[index])
+        } else {
+            self.second.get(index - self.first.len())
+        }
+    }
+
+    ///Returns the first element of the view.
+    pub fn first(&self) -> Option<&'a T> {
+        self.get(0)
+    }
+
+    ///Returns the last element of the view.
+    pub fn last(&self) -> Option<&'a T> {
+        self.second.last().or_else(|| self.first.last())
+    }
+
+    ///Returns an iterator over elements of the view.
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter::new(self.first, self.second)
+    }
+
+    ///Returns the two slices containing elements of the view, the second one may be empty.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.first, self.second)
+    }
+
+    ///Returns a view of a range of elements of this view.
+    pub fn range<R>(&self, range: R) -> Self where R: RangeBounds<usize> {
+        Self::new(self.first, self.second, range)
+    }
+}
+
+impl<'a, T> Clone for CircularSlice<'a, T>{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for CircularSlice<'a, T>{}
+
+impl<'a, T> fmt::Debug for CircularSlice<'a, T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> Index<usize> for CircularSlice<'a, T>{
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(val) => val,
+            None => panic!("Index {} is out of bounds for length {}.", index, self.len())
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for CircularSlice<'a, T>{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, 'b, T> PartialEq<CircularSlice<'b, T>> for CircularSlice<'a, T> where T: PartialEq {
+    fn eq(&self, other: &CircularSlice<'b, T>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, T> Eq for CircularSlice<'a, T> where T: Eq {}
+
+impl<'a, T> PartialEq<[T]> for CircularSlice<'a, T> where T: PartialEq {
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, 'b, T> PartialEq<&'b [T]> for CircularSlice<'a, T> where T: PartialEq {
+    fn eq(&self, other: &&'b [T]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, 'b, T> PartialOrd<CircularSlice<'b, T>> for CircularSlice<'a, T> where T: PartialOrd {
+    fn partial_cmp(&self, other: &CircularSlice<'b, T>) -> Option<core::cmp::Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<'a, T> Ord for CircularSlice<'a, T> where T: Ord {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout(){
+        let layout = Layout::new(2, 3);
+        assert_eq!(layout, Layout{first: 0..2, second: 2..5});
+        assert_eq!(layout.logical_to_physical(1), Some((0, 1)));
+        assert_eq!(layout.logical_to_physical(2), Some((1, 0)));
+        assert_eq!(layout.logical_to_physical(5), None);
+        assert_eq!(layout.physical_to_logical(1, 2), Some(4));
+        assert_eq!(layout.physical_to_logical(1, 3), None);
+        assert_eq!(layout.physical_to_logical(2, 0), None);
+        assert_eq!(Layout::new(0, 0).logical_to_physical(0), None);
+    }
+
+    #[test]
+    fn test_new(){
+        let (a, b) = (&[1, 2, 3][..], &[4, 5][..]);
+        assert_eq!(CircularSlice::new(a, b, ..).as_slices(), (a, b));
+        assert_eq!(CircularSlice::new(a, b, 1..2).as_slices(), (&[2][..], &[][..]));
+        assert_eq!(CircularSlice::new(a, b, 2..=3).as_slices(), (&[3][..], &[4][..]));
+        assert_eq!(CircularSlice::new(a, b, 3..).as_slices(), (&[4, 5][..], &[][..]));
+        assert_eq!(CircularSlice::new(a, b, 5..).as_slices(), (&[][..], &[][..]));
+        assert!(CircularSlice::new(a, b, 3..3).is_empty());
+    }
+
+    #[test]
+    fn test_access(){
+        let s = CircularSlice::new(&[1, 2, 3][..], &[4, 5][..], 1..);
+        assert_eq!(s.len(), 4);
+        assert_eq!(s.get(2), Some(&4));
+        assert_eq!(s.get(4), None);
+        assert_eq!(s.first(), Some(&2));
+        assert_eq!(s.last(), Some(&5));
+        assert_eq!(s[3], 5);
+        assert_eq!(s.range(1..3), [3, 4].as_ref());
+        assert_eq!(format!("{:?}", s), "[2, 3, 4, 5]");
+        assert_eq!(s.into_iter().rev().cloned().collect::<Vec<_>>(), vec![5, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_compare(){
+        let a = CircularSlice::new(&[1, 2][..], &[3][..], ..);
+        let b = CircularSlice::new(&[1][..], &[2, 3][..], ..);
+        let c = CircularSlice::new(&[1][..], &[2, 4][..], ..);
+        assert_eq!(a, b);
+        assert!(a < c);
+        assert_eq!(a.cmp(&b), core::cmp::Ordering::Equal);
+        assert!(a != c.range(..2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_out_of_bounds(){
+        CircularSlice::new(&[1, 2][..], &[3][..], 1..4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds(){
+        let s = CircularSlice::new(&[1, 2][..], &[3][..], 1..);
+        let _ = s[2];
+    }
+}
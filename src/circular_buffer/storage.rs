@@ -0,0 +1,53 @@
+use core::mem::MaybeUninit;
+
+/**
+Memory that stores elements of a `GenericCircularBuffer`.
+
+The buffer keeps track of which slots are initialized, the storage only provides them.
+It is implemented for arrays, borrowed slices and boxed slices of `MaybeUninit<T>`,
+so the same buffer implementation can work on the stack, in static variables,
+in caller-provided memory (DMA buffers, arenas) or on the heap.
+
+# Safety
+
+Every call of `slots()` and `slots_mut()` needs to return the same memory with the same length.
+The buffer relies on this to find elements that it initialized before.
+*/
+pub unsafe trait Storage<T> {
+    ///Returns all slots of the storage.
+    fn slots(&self) -> &[MaybeUninit<T>];
+
+    ///Returns all slots of the storage as a mutable slice.
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>];
+}
+
+unsafe impl<T, const N: usize> Storage<T> for [MaybeUninit<T>; N] {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+unsafe impl<T> Storage<T> for &mut [MaybeUninit<T>] {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<T> Storage<T> for Box<[MaybeUninit<T>]> {
+    fn slots(&self) -> &[MaybeUninit<T>] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        self
+    }
+}
@@ -0,0 +1,214 @@
+/*!
+Model-based testing of `CircularBuffer`.
+
+Operations are applied both to a `CircularBuffer` and to a simple model built on top of
+`VecDeque` with the same capacity rules. After every operation both collections
+need to contain the same elements in the same order.
+
+The module is available for tests of this crate and, with the `test-util` feature,
+for users who want to add their own regression cases.
+
+# Example
+
+Requires the `test-util` feature, run with `cargo test --features test-util`:
+
+```
+use advanced_collections::circular_buffer::test_util::{check_ops, check_random, Op};
+
+fn main(){
+    //a regression case
+    check_ops(2, &[Op::PushBack(1), Op::PushFront(2), Op::PushBack(3), Op::Resize(1), Op::PopFront]);
+
+    //randomized operation sequences
+    check_random(42, 100, 200);
+}
+```
+*/
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use super::circular::CircularBuffer;
+use crate::test_util::XorShift;
+
+///A single operation applied to a buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<T> {
+    ///Pushes the element to the back, removing the front one if the buffer is full.
+    PushBack(T),
+    ///Pushes the element to the front, removing the back one if the buffer is full.
+    PushFront(T),
+    ///Removes the element from the back.
+    PopBack,
+    ///Removes the element from the front.
+    PopFront,
+    ///Changes the capacity, keeping the newest elements.
+    Resize(usize),
+    ///Removes all elements.
+    Clear,
+    ///Compares content of both collections using iterators.
+    Iter
+}
+
+/**
+A reference implementation of a circular buffer based on `VecDeque`.
+*/
+#[derive(Debug, Clone)]
+pub struct Model<T> {
+    deque: VecDeque<T>,
+    capacity: usize
+}
+
+impl<T> Model<T> {
+    ///Creates an empty model with the given capacity.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            deque: VecDeque::with_capacity(capacity),
+            capacity
+        }
+    }
+
+    ///Applies an operation, returning the popped element if there is one.
+    pub fn apply(&mut self, op: Op<T>) -> Option<T> {
+        match op {
+            Op::PushBack(val) => {
+                if self.capacity > 0 {
+                    if self.deque.len() == self.capacity {
+                        self.deque.pop_front();
+                    }
+                    self.deque.push_back(val);
+                }
+                None
+            },
+            Op::PushFront(val) => {
+                if self.capacity > 0 {
+                    if self.deque.len() == self.capacity {
+                        self.deque.pop_back();
+                    }
+                    self.deque.push_front(val);
+                }
+                None
+            },
+            Op::PopBack => self.deque.pop_back(),
+            Op::PopFront => self.deque.pop_front(),
+            Op::Resize(capacity) => {
+                //the newest elements are kept
+                while self.deque.len() > capacity {
+                    self.deque.pop_front();
+                }
+                self.capacity = capacity;
+                None
+            },
+            Op::Clear => {
+                self.deque.clear();
+                None
+            },
+            Op::Iter => None
+        }
+    }
+
+    ///Returns the content of the model as a slice pair, from the front to back.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.deque.as_slices()
+    }
+}
+
+///Applies an operation to the buffer, returning the popped element if there is one.
+pub fn apply<T>(buf: &mut CircularBuffer<T>, op: Op<T>) -> Option<T> {
+    match op {
+        Op::PushBack(val) => {
+            buf.push_back(val);
+            None
+        },
+        Op::PushFront(val) => {
+            buf.push_front(val);
+            None
+        },
+        Op::PopBack => buf.pop_back(),
+        Op::PopFront => buf.pop_front(),
+        Op::Resize(capacity) => {
+            buf.resize(capacity);
+            None
+        },
+        Op::Clear => {
+            buf.clear();
+            None
+        },
+        Op::Iter => None
+    }
+}
+
+/**
+Applies operations to a new buffer and to the model, panicking on the first difference.
+
+Panic messages contain the index of the failing operation.
+*/
+pub fn check_ops<T>(capacity: usize, ops: &[Op<T>]) where T: Clone + PartialEq + Debug {
+    let mut buf = CircularBuffer::new(capacity);
+    let mut model = Model::new(capacity);
+    for (idx, op) in ops.iter().enumerate() {
+        let is_iter = *op == Op::Iter;
+        let actual = apply(&mut buf, op.clone());
+        let expected = model.apply(op.clone());
+        assert_eq!(actual, expected, "returned value differs after operation {}: {:?}", idx, op);
+        assert_eq!(buf.len(), model.deque.len(), "length differs after operation {}: {:?}", idx, op);
+        assert_eq!(buf.capacity(), model.capacity, "capacity differs after operation {}: {:?}", idx, op);
+        if is_iter {
+            assert!(buf.iter().eq(model.deque.iter()), "content differs after operation {}: {:?}", idx, op);
+            assert!(buf.iter().rev().eq(model.deque.iter().rev()), "reversed content differs after operation {}: {:?}", idx, op);
+        }
+        assert_eq!(buf.first(), model.deque.front(), "front differs after operation {}: {:?}", idx, op);
+        assert_eq!(buf.last(), model.deque.back(), "back differs after operation {}: {:?}", idx, op);
+    }
+    let (a, b) = buf.slices();
+    let (ma, mb) = model.as_slices();
+    assert_eq!([a, b].concat(), [ma, mb].concat(), "content differs after all operations");
+}
+
+///Generates a pseudo-random sequence of operations.
+pub fn random_ops(rng: &mut XorShift, len: usize, max_capacity: usize) -> Vec<Op<u32>> {
+    (0..len).map(|_| match rng.below(16) {
+        0..=4 => Op::PushBack(rng.next_u64() as u32),
+        5..=7 => Op::PushFront(rng.next_u64() as u32),
+        8..=9 => Op::PopFront,
+        10 | 11 => Op::PopBack,
+        12 => Op::Resize(rng.below(max_capacity as u64 + 1) as usize),
+        13 => Op::Clear,
+        _ => Op::Iter
+    }).collect()
+}
+
+/**
+Runs `cases` randomized operation sequences of length `len` derived from the seed.
+
+Panics with the seed of the failing case, which can be used to reproduce it.
+*/
+pub fn check_random(seed: u64, cases: usize, len: usize) {
+    let mut rng = XorShift::new(seed);
+    for _ in 0..cases {
+        let case_seed = rng.next_u64();
+        let mut case_rng = XorShift::new(case_seed);
+        let capacity = case_rng.below(8) as usize;
+        let ops = random_ops(&mut case_rng, len, 8);
+        let res = ::std::panic::catch_unwind(|| check_ops(capacity, &ops));
+        if res.is_err() {
+            panic!("Model check failed for case seed {}", case_seed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random(){
+        check_random(1, 300, 100);
+    }
+
+    #[test]
+    fn test_regressions(){
+        check_ops(0, &[Op::PushBack(1), Op::PushFront(2), Op::PopBack, Op::Resize(1), Op::PushFront(3), Op::Iter]);
+        check_ops(1, &[Op::PushBack(1), Op::PushBack(2), Op::PushFront(3), Op::Iter, Op::Resize(0), Op::PopFront]);
+        check_ops(3, &[Op::PushFront(1), Op::PushFront(2), Op::PushBack(3), Op::PushBack(4), Op::Resize(5), Op::PushFront(5), Op::Iter]);
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+use super::circular::CircularBuffer;
+use super::iter::Iter;
+
+/**
+A circular buffer of recent events, bounded both by the capacity and by the age of elements.
+
+Every element is stored with the time when it was pushed. Elements older than `max_age`
+are removed from the front when a new element is pushed or when `expire()` is called.
+If the buffer is full, pushing removes the oldest element, the same way as in `CircularBuffer`.
+This makes it suitable for rate limiting and sliding windows of recent events.
+
+Elements are expected to be pushed in chronological order. The `_at` variants of functions
+take the current time as an argument, which is useful for replaying events and in tests.
+
+# Example
+
+```
+use advanced_collections::circular_buffer::TimedCircularBuffer;
+use std::time::{Duration, Instant};
+
+fn main(){
+    //at most 3 requests per second
+    let mut requests = TimedCircularBuffer::new(3, Duration::from_secs(1));
+    let start = Instant::now();
+    for ms in [0, 100, 200, 300, 1150].iter() {
+        let now = start + Duration::from_millis(*ms);
+        requests.expire_at(now);
+        if requests.is_full() {
+            //rejected request
+            assert_eq!(*ms, 300);
+        } else {
+            requests.push_at(now, *ms);
+        }
+    }
+    //the request from 100 ms expired
+    assert_eq!(requests.iter().map(|(_, ms)| *ms).collect::<Vec<_>>(), vec![200, 1150]);
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct TimedCircularBuffer<T> {
+    buffer: CircularBuffer<(Instant, T)>,
+    max_age: Duration
+}
+
+impl<T> TimedCircularBuffer<T> {
+    ///Creates an empty buffer with the given capacity and the maximal age of elements.
+    pub fn new(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            buffer: CircularBuffer::new(capacity),
+            max_age
+        }
+    }
+
+    ///Pushes an element with the current time, removing expired elements first.
+    pub fn push(&mut self, val: T) {
+        self.push_at(Instant::now(), val);
+    }
+
+    ///Pushes an element with the given time, removing elements expired at that time first.
+    pub fn push_at(&mut self, now: Instant, val: T) {
+        self.expire_at(now);
+        self.buffer.push_back((now, val));
+    }
+
+    ///Removes elements older than `max_age` and returns their number.
+    pub fn expire(&mut self) -> usize {
+        self.expire_at(Instant::now())
+    }
+
+    /**
+    Removes elements that are older than `max_age` at the given time and returns their number.
+
+    Elements are removed from the front until the first element that has not expired.
+    */
+    pub fn expire_at(&mut self, now: Instant) -> usize {
+        let max_age = self.max_age;
+        let mut removed = 0;
+        while self.buffer.pop_front_if(|(time, _)| now.saturating_duration_since(*time) > max_age).is_some() {
+            removed += 1;
+        }
+        removed
+    }
+
+    ///Returns the maximal age of elements.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    ///Changes the maximal age of elements. Elements are removed by the next push or `expire()`.
+    pub fn set_max_age(&mut self, max_age: Duration) {
+        self.max_age = max_age;
+    }
+
+    ///Returns the number of elements, including the ones that expired since the last push or `expire()`.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    ///Checks if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    ///Checks if the buffer is full.
+    pub fn is_full(&self) -> bool {
+        self.buffer.is_full()
+    }
+
+    ///Returns the maximal number of elements.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    ///Returns the oldest element and its time.
+    pub fn front(&self) -> Option<&(Instant, T)> {
+        self.buffer.first()
+    }
+
+    ///Returns the newest element and its time.
+    pub fn back(&self) -> Option<&(Instant, T)> {
+        self.buffer.last()
+    }
+
+    ///Removes the oldest element.
+    pub fn pop_front(&mut self) -> Option<(Instant, T)> {
+        self.buffer.pop_front()
+    }
+
+    ///Removes all elements.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    ///Returns an iterator over elements and their times, from the oldest to the newest.
+    pub fn iter(&self) -> Iter<'_, (Instant, T)> {
+        self.buffer.iter()
+    }
+
+    ///Returns the buffer containing elements and their times.
+    pub fn buffer(&self) -> &CircularBuffer<(Instant, T)> {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire(){
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut buf = TimedCircularBuffer::new(4, Duration::from_millis(100));
+        for ms in 0..6 {
+            buf.push_at(at(ms * 10), ms);
+        }
+        //capacity bound removed the first two elements
+        assert_eq!(buf.iter().map(|&(_, v)| v).collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+        //an element exactly max_age old is kept
+        assert_eq!(buf.expire_at(at(120)), 0);
+        assert_eq!(buf.expire_at(at(131)), 2);
+        assert_eq!(buf.front().map(|&(_, v)| v), Some(4));
+        buf.push_at(at(500), 9);
+        assert_eq!(buf.len(), 1);
+        assert_eq!(buf.back(), Some(&(at(500), 9)));
+        //the time before the first element does not expire anything
+        assert_eq!(buf.expire_at(start), 0);
+        buf.set_max_age(Duration::from_millis(0));
+        assert_eq!(buf.expire_at(at(501)), 1);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_push_now(){
+        let mut buf = TimedCircularBuffer::new(2, Duration::from_secs(3600));
+        buf.push("a");
+        buf.push("b");
+        buf.push("c");
+        assert_eq!(buf.expire(), 0);
+        assert!(buf.is_full());
+        assert_eq!(buf.pop_front().map(|(_, v)| v), Some("b"));
+        assert!(buf.buffer().first().unwrap().0 <= Instant::now());
+        buf.clear();
+        assert_eq!(buf.capacity(), 2);
+        assert_eq!(buf.max_age(), Duration::from_secs(3600));
+    }
+}
@@ -1,10 +1,16 @@
+use std::borrow::Borrow;
+use std::fmt::{Display, Write};
 use std::hash::{BuildHasher, Hash};
 use std::iter::{Extend, FromIterator};
 use std::default::Default;
 use std::ops::{Add, AddAssign, Deref, DerefMut, Sub, SubAssign};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, TryReserveError};
 use std::collections::hash_map::RandomState;
 use std::collections::hash_map::Entry;
+use std::vec::IntoIter as VecIntoIter;
+use super::sink::CounterSink;
+use super::diff::CounterDiff;
+use crate::disjoint_set::DisjointSet;
 
 type IntoIter<T> = ::std::collections::hash_map::IntoIter<T, usize>;
 type Iter<'a, T> = ::std::collections::hash_map::Iter<'a, T, usize>;
@@ -117,6 +123,42 @@ where
         }
     }
 
+    ///Returns a reference to the Counter's BuildHasher.
+    pub fn hasher(&self) -> &S {
+        self.counter.hasher()
+    }
+
+    /**
+    Moves all elements into a new Counter that uses a different BuildHasher type.
+
+    No intermediate collection is created - elements are rehashed directly into
+    a Counter with the required capacity.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::{Counter, FastCounter};
+    use std::iter::FromIterator;
+
+    fn main(){
+        let ingress: Counter<&str> = Counter::from_iter(vec!["a", "b", "a"]);
+        let internal: FastCounter<&str> = ingress.rehash();
+        assert_eq!(internal.get("a"), Some(&2));
+        assert_eq!(internal.len(), 2);
+    }
+    ```
+    */
+    pub fn rehash<S2>(self) -> Counter<T, S2> where S2: BuildHasher + Default {
+        self.rehash_with_hasher(S2::default())
+    }
+
+    ///Moves all elements into a new Counter that uses the provided BuildHasher.
+    pub fn rehash_with_hasher<S2>(self, hash_builder: S2) -> Counter<T, S2> where S2: BuildHasher {
+        let mut counter = HashMap::with_capacity_and_hasher(self.counter.len(), hash_builder);
+        counter.extend(self.counter);
+        Counter { counter }
+    }
+
     /**
     Creates Counter from the provided HashMap with the same BuildHasher type.
 
@@ -143,6 +185,48 @@ where
         Self { counter: rhs }
     }
 
+    /**
+    Merges multiple Counters into one, summing counts of equal elements.
+
+    The largest Counter is reused as the result and memory for the remaining elements is
+    reserved only once, which makes this function suitable for reduce steps of map-reduce
+    style counting.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let a: Counter<char> = Counter::from_iter("abc".chars());
+        let b: Counter<char> = Counter::from_iter("bcd".chars());
+        let c: Counter<char> = Counter::from_iter("cde".chars());
+        let m = Counter::merge_all(vec![a, b, c]);
+        assert_eq!(m.len(), 5);
+        assert_eq!(m[&'c'], 3);
+    }
+    ```
+    */
+    pub fn merge_all<I>(counters: I) -> Self
+    where
+        I: IntoIterator<Item = Counter<T, S>>,
+        S: Default,
+    {
+        let mut counters: Vec<Self> = counters.into_iter().collect();
+        let largest = match (0..counters.len()).max_by_key(|&i| counters[i].len()) {
+            None => return Self::new(),
+            Some(i) => i
+        };
+        let mut result = counters.swap_remove(largest);
+        let additional = counters.iter().map(|c| c.len()).sum();
+        result.counter.reserve(additional);
+        for other in counters {
+            result += other;
+        }
+        result
+    }
+
     /**
     Returns a Vec with sorted tuples - a element plus its count.
 
@@ -168,6 +252,38 @@ where
         res
     }
 
+    /**
+    Returns an iterator over elements sorted from the most common,
+    yielding each element, its count and its fraction of the total count.
+
+    The total is calculated once when the iterator is created,
+    so all fractions are consistent and sum up to 1.
+    Fractions are calculated lazily, one element at a time.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        c.extend("abaa".chars());
+        let mut it = c.most_common_into_iter_with_total();
+        assert_eq!(it.total(), 4);
+        assert_eq!(it.next(), Some(('a', 3, 0.75)));
+        assert_eq!(it.next(), Some(('b', 1, 0.25)));
+        assert_eq!(it.next(), None);
+    }
+    ```
+    */
+    pub fn most_common_into_iter_with_total(self) -> MostCommonWithTotal<T> {
+        let total = self.counter.values().sum();
+        MostCommonWithTotal {
+            iter: self.into_most_common().into_iter(),
+            total
+        }
+    }
+
     /**
     Returns a Vec with sorted tuples - a element plus its count.
 
@@ -199,6 +315,347 @@ where
         res
     }
 
+    /**
+    Returns a Vec with sorted tuples - a reference to an element plus its count.
+
+    Works like `most_common()` but does not clone elements.
+    The collection starts with the most common elements.
+    Elements with equal counts are ordered arbitrarily.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<String> = Counter::new();
+        c.extend("b a b c a b".split(' ').map(String::from));
+        let mc = c.most_common_ref();
+        assert_eq!(mc[0], (&"b".to_string(), 3));
+        assert_eq!(mc[1], (&"a".to_string(), 2));
+    }
+    ```
+    */
+    pub fn most_common_ref(&self) -> Vec<(&T, usize)> {
+        let mut res: Vec<(&T, usize)> = self.counter
+            .iter()
+            .map(|(key, &val)| (key, val))
+            .collect();
+        res.sort_unstable_by_key(|&(_, val)| ::std::cmp::Reverse(val));
+        res
+    }
+
+    /**
+    Returns a Vec with `n` most common elements, represented by references, plus their counts.
+
+    Only the returned elements are sorted, so it is faster than `most_common_ref()`
+    when `n` is small compared to the number of distinct elements.
+
+    **Complexity:** O(k + n log n)
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        c.extend("abbcccdddd".chars());
+        assert_eq!(c.most_common_ref_n(2), vec![(&'d', 4), (&'c', 3)]);
+    }
+    ```
+    */
+    pub fn most_common_ref_n(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut res: Vec<(&T, usize)> = self.counter
+            .iter()
+            .map(|(key, &val)| (key, val))
+            .collect();
+        if n < res.len() {
+            if n == 0 {
+                return Vec::new();
+            }
+            res.select_nth_unstable_by_key(n - 1, |&(_, val)| ::std::cmp::Reverse(val));
+            res.truncate(n);
+        }
+        res.sort_unstable_by_key(|&(_, val)| ::std::cmp::Reverse(val));
+        res
+    }
+
+    /**
+    Returns a Vec with `n` most common elements plus their counts.
+
+    Works like `most_common_ref_n()`, but clones the returned elements.
+    The collection starts with the most common elements.
+
+    **Complexity:** O(k + n log n)
+
+    # Example
+
+    ```
+    use advanced_collections::counter::FastCounter;
+
+    fn main(){
+        let mut c: FastCounter<char> = FastCounter::new();
+        c.extend("abbcccdddd".chars());
+        assert_eq!(c.most_common_n(2), vec![('d', 4), ('c', 3)]);
+    }
+    ```
+    */
+    pub fn most_common_n(&self, n: usize) -> Vec<(T, usize)>
+    where
+        T: Clone,
+    {
+        self.most_common_ref_n(n)
+            .into_iter()
+            .map(|(key, count)| (key.clone(), count))
+            .collect()
+    }
+
+    /**
+    Returns `n` most common elements and their counts as two separate Vecs.
+
+    Both Vecs start with the most common element, which makes the result
+    ready to be used as labels and values of a chart or a table.
+
+    **Complexity:** O(k + n log n)
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c: Counter<char> = Counter::new();
+        c.extend("abbcccdddd".chars());
+        let (labels, values) = c.to_label_value_vecs(3);
+        assert_eq!(labels, vec![&'d', &'c', &'b']);
+        assert_eq!(values, vec![4, 3, 2]);
+    }
+    ```
+    */
+    pub fn to_label_value_vecs(&self, n: usize) -> (Vec<&T>, Vec<usize>) {
+        self.most_common_ref_n(n).into_iter().unzip()
+    }
+
+    /**
+    Returns a text bar chart of `n` most common elements, one element per line.
+
+    Each line contains the element, a bar made of `#` characters and the count.
+    The bar of the most common element is `width` characters long and other bars are scaled
+    proportionally, but every element with a non-zero count gets at least one character.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c: Counter<char> = Counter::new();
+        c.extend("abbccccdddddddd".chars());
+        assert_eq!(c.display_bars(3, 8), "d | ######## 8\nc | #### 4\nb | ## 2\n");
+    }
+    ```
+    */
+    pub fn display_bars(&self, n: usize, width: usize) -> String
+    where
+        T: Display,
+    {
+        let (labels, values) = self.to_label_value_vecs(n);
+        let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+        let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let max = values.first().cloned().unwrap_or(0);
+        let mut res = String::new();
+        for (label, &count) in labels.iter().zip(values.iter()) {
+            //the product of a large count and the width does not fit into usize
+            let mut bar = (count as u128 * width as u128 / max.max(1) as u128) as usize;
+            if bar == 0 && count > 0 && width > 0 {
+                bar = 1;
+            }
+            let _ = writeln!(res, "{:<w$} | {} {}", label, "#".repeat(bar), count, w = label_width);
+        }
+        res
+    }
+
+    /**
+    Sums counts of equivalent elements, using subsets of the disjoint set as equivalence classes.
+
+    Each element of the result is the representative of its subset in the disjoint set
+    and its count is the sum of counts of all elements of that subset.
+    Elements that do not belong to the disjoint set are their own representatives.
+
+    **Complexity:** O(n + m), where m is the number of elements of the disjoint set
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use advanced_collections::disjoint_set::DisjointSet;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let c: Counter<&str> = Counter::from_iter(vec!["NY", "New York", "NYC", "Boston", "LA"]);
+        let mut aliases: DisjointSet<&str> = DisjointSet::new();
+        aliases.union("NY", "New York");
+        aliases.union("NY", "NYC");
+        let grouped = c.group_by_sets(&mut aliases);
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(grouped.values().max(), Some(&3));
+        assert_eq!(grouped[&"Boston"], 1);
+    }
+    ```
+    */
+    pub fn group_by_sets<S2>(&self, sets: &mut DisjointSet<T, S2>) -> Self
+    where
+        T: Clone,
+        S: Default,
+        S2: BuildHasher,
+    {
+        let roots: HashMap<&T, &T> = sets.iter_with_roots().collect();
+        let mut res = Self::new();
+        for (key, &count) in self.counter.iter() {
+            let rep = roots.get(key).cloned().unwrap_or(key);
+            *res.counter.entry(rep.clone()).or_insert(0) += count;
+        }
+        res
+    }
+
+    /**
+    Returns a map from a count to the list of elements with that count.
+
+    Elements in lists are ordered arbitrarily.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        c.extend("abcaab".chars());
+        let inv = c.invert();
+        assert_eq!(inv[&3], vec!['a']);
+        assert_eq!(inv[&2], vec!['b']);
+        assert_eq!(inv[&1], vec!['c']);
+    }
+    ```
+    */
+    pub fn invert(&self) -> HashMap<usize, Vec<T>>
+    where
+        T: Clone,
+    {
+        let mut res: HashMap<usize, Vec<T>> = HashMap::new();
+        for (key, &val) in self.counter.iter() {
+            res.entry(val).or_default().push(key.clone());
+        }
+        res
+    }
+
+    /**
+    Returns a sorted map from a count to the list of elements with that count.
+
+    Useful for traversing elements from the least to the most common.
+    Elements in lists are ordered arbitrarily.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        c.extend("abcaab".chars());
+        let inv = c.invert_into_btree();
+        let counts: Vec<usize> = inv.keys().cloned().collect();
+        assert_eq!(counts, vec![1, 2, 3]);
+    }
+    ```
+    */
+    pub fn invert_into_btree(&self) -> BTreeMap<usize, Vec<T>>
+    where
+        T: Clone,
+    {
+        let mut res: BTreeMap<usize, Vec<T>> = BTreeMap::new();
+        for (key, &val) in self.counter.iter() {
+            res.entry(val).or_default().push(key.clone());
+        }
+        res
+    }
+
+    /**
+    Exports elements and their counts into two parallel vectors.
+
+    The order of elements is arbitrary but the same in both vectors:
+    `counts[i]` is the count of `keys[i]`.
+    Dense counts can be processed with vectorized numeric code.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        c.extend("abbccc".chars());
+        let (keys, counts) = c.counts_vec();
+        let total: usize = counts.iter().sum();
+        let shares: Vec<f64> = counts.iter().map(|&n| n as f64 / total as f64).collect();
+        let b = keys.iter().position(|&&k| k == 'b').unwrap();
+        assert_eq!(shares[b], 2.0 / 6.0);
+    }
+    ```
+    */
+    pub fn counts_vec(&self) -> (Vec<&T>, Vec<usize>) {
+        self.counter.iter().map(|(key, &val)| (key, val)).unzip()
+    }
+
+    /**
+    Returns counts of all provided keys, with 0 for keys that were never counted.
+
+    Counts are returned in the order of keys.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<String> = Counter::new();
+        c.extend("to be or not to be".split(' ').map(String::from));
+        let vocabulary = ["be", "to", "is"];
+        assert_eq!(c.get_many(vocabulary.iter().copied()), vec![2, 2, 0]);
+    }
+    ```
+    */
+    pub fn get_many<'a, Q, I>(&self, keys: I) -> Vec<usize>
+    where
+        T: Borrow<Q>,
+        Q: 'a + Hash + Eq + ?Sized,
+        I: IntoIterator<Item=&'a Q>
+    {
+        keys.into_iter().map(|key| self.counter.get(key).cloned().unwrap_or(0)).collect()
+    }
+
+    /**
+    Tries to reserve capacity for at least `additional` more distinct elements.
+
+    Returns an error instead of aborting if the capacity overflows or the allocation fails.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::new();
+        assert!(c.try_reserve(10).is_ok());
+        assert!(c.try_reserve(usize::MAX).is_err());
+    }
+    ```
+    */
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.counter.try_reserve(additional)
+    }
+
     /**
     Adds a single element count to the collection.
 
@@ -218,8 +675,357 @@ where
     pub fn push(&mut self, val: T){
         *self.counter.entry(val).or_insert(0) += 1;
     }
+
+    /**
+    Adds a single element count to the collection using a borrowed form of the element.
+
+    The element is converted into its owned form only if it is not present in the collection yet,
+    so for example counting `&str` in `Counter<String>` allocates only for new words.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<String> = Counter::new();
+        c.push_borrowed("word");
+        c.push_borrowed("word");
+        assert_eq!(c.count("word"), 2);
+    }
+    ```
+    */
+    pub fn push_borrowed<Q>(&mut self, val: &Q)
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        match self.counter.get_mut(val) {
+            Some(count) => *count += 1,
+            None => {
+                self.counter.insert(val.to_owned(), 1);
+            }
+        }
+    }
+
+    /**
+    Returns a mutable reference to the count of the element, inserting it with count 0 if needed.
+
+    The element is looked up using its borrowed form and the owned element is created
+    using the provided function only if it is not present in the collection yet.
+    The created element needs to be equal to the borrowed one.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut c:Counter<String> = Counter::new();
+        let mut allocations = 0;
+        for word in "the cat and the hat and the bat".split(' ') {
+            //the owned key is allocated only for the first occurrence of a word
+            *c.count_or_insert_with(word, || { allocations += 1; word.to_string() }) += 1;
+        }
+        assert_eq!(c.count("the"), 3);
+        assert_eq!(c.count("bat"), 1);
+        assert_eq!(allocations, 5);
+    }
+    ```
+    */
+    pub fn count_or_insert_with<Q, F>(&mut self, val: &Q, make_val: F) -> &mut usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        F: FnOnce() -> T,
+    {
+        if self.counter.contains_key(val) {
+            return self.counter.get_mut(val).expect("Element exists in the collection");
+        }
+        let owned = make_val();
+        debug_assert!(owned.borrow() == val, "Created element differs from the borrowed one");
+        self.counter.entry(owned).or_insert(0)
+    }
+
+    /**
+    Returns the count of the given element, 0 if the element is not present.
+
+    The element may be any borrowed form of the counted type.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let c:Counter<String> = Counter::from_iter(vec!["a".to_string(), "b".to_string(), "a".to_string()]);
+        assert_eq!(c.count("a"), 2);
+        assert_eq!(c.count("c"), 0);
+    }
+    ```
+    */
+    pub fn count<Q>(&self, val: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counter.get(val).cloned().unwrap_or(0)
+    }
+
+    /**
+    Removes the given element from the collection, returning its count if it was present.
+
+    The element may be any borrowed form of the counted type.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let mut c:Counter<String> = Counter::from_iter(vec!["a".to_string(), "a".to_string()]);
+        assert_eq!(c.remove("a"), Some(2));
+        assert_eq!(c.remove("a"), None);
+    }
+    ```
+    */
+    pub fn remove<Q>(&mut self, val: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.counter.remove(val)
+    }
+
+    /**
+    Removes the given element from the collection and returns its prior count.
+
+    This is an alias of `remove()` that reads naturally in state transitions.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::from_iter("aab".chars());
+        assert_eq!(c.take(&'a'), Some(2));
+        assert_eq!(c.take(&'a'), None);
+    }
+    ```
+    */
+    pub fn take<Q>(&mut self, val: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove(val)
+    }
+
+    /**
+    Sets the count of the given element and returns its prior count.
+
+    Setting the count to 0 removes the element, so the counter never contains zero counts.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::from_iter("aab".chars());
+        assert_eq!(c.replace('a', 5), Some(2));
+        assert_eq!(c.replace('c', 1), None);
+        assert_eq!(c.replace('b', 0), Some(1));
+        assert!(!c.contains_key(&'b'));
+    }
+    ```
+    */
+    pub fn replace(&mut self, val: T, count: usize) -> Option<usize> {
+        if count == 0 {
+            self.counter.remove(&val)
+        } else {
+            self.counter.insert(val, count)
+        }
+    }
+
+    /**
+    Multiplies all counts by the given factor, rounding down,
+    and removes elements whose counts fall below `min_count`.
+
+    Applied periodically, it gives older occurrences smaller weights than recent ones.
+    Panics if the factor is negative or not a number.
+
+    **Complexity:** O(k)
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let mut c:Counter<char> = Counter::from_iter("aaaaaaaaaabbbbc".chars());
+        c.decay(0.5, 2);
+        assert_eq!(c.count(&'a'), 5);
+        assert_eq!(c.count(&'b'), 2);
+        assert!(!c.contains_key(&'c'));
+    }
+    ```
+    */
+    pub fn decay(&mut self, factor: f64, min_count: usize) {
+        if factor.is_nan() || factor < 0.0 {
+            panic!("Decay factor needs to be a non-negative number.");
+        }
+        self.counter.retain(|_, count| {
+            *count = scale_down(*count, factor);
+            *count >= min_count && *count > 0
+        });
+    }
+
+    /**
+    Lists elements that are counted differently by this and the other counter.
+
+    Useful in tests, where printing the difference of large counters
+    is more readable than printing both of them.
+
+    **Complexity:** O(k + l), where l is the number of elements of the other counter
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let expected: Counter<&str> = Counter::from_iter(vec!["GET", "GET", "POST"]);
+        let actual: Counter<&str> = Counter::from_iter(vec!["GET", "POST", "PUT"]);
+        let diff = expected.diff(&actual);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.to_string(), "+ PUT: 1\n~ GET: 2 -> 1\n");
+        assert!(expected.diff(&expected).is_empty());
+    }
+    ```
+    */
+    pub fn diff<'a, S2>(&'a self, other: &'a Counter<T, S2>) -> CounterDiff<'a, T>
+    where
+        S2: BuildHasher,
+    {
+        let mut only_in_self = Vec::new();
+        let mut changed = Vec::new();
+        for (key, &count) in self.counter.iter() {
+            match other.counter.get(key) {
+                None => only_in_self.push((key, count)),
+                Some(&other_count) if other_count != count => changed.push((key, count, other_count)),
+                Some(_) => ()
+            }
+        }
+        let only_in_other = other.counter.iter()
+            .filter(|(key, _)| !self.counter.contains_key(*key))
+            .map(|(key, &count)| (key, count))
+            .collect();
+        CounterDiff::new(only_in_self, only_in_other, changed)
+    }
+
+    /**
+    Checks if counts of every element in both counters differ by at most `tolerance`.
+
+    Elements missing from a counter have the count of 0.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+    use std::iter::FromIterator;
+
+    fn main(){
+        let a: Counter<char> = Counter::from_iter("aaab".chars());
+        let b: Counter<char> = Counter::from_iter("aacc".chars());
+        assert!(!a.approx_eq(&b, 1));
+        assert!(a.approx_eq(&b, 2));
+    }
+    ```
+    */
+    pub fn approx_eq<S2>(&self, other: &Counter<T, S2>, tolerance: usize) -> bool
+    where
+        S2: BuildHasher,
+    {
+        let within = |a: usize, b: usize| a.max(b) - a.min(b) <= tolerance;
+        self.counter.iter().all(|(key, &count)| within(count, other.count(key)))
+            && other.counter.iter().all(|(key, &count)| within(count, self.count(key)))
+    }
+
+    /**
+    Moves all counts into the sink, leaving this counter empty.
+
+    The content of the counter is swapped with an empty map before the sink is called,
+    so the counter is immediately ready to collect new counts.
+    Flushed counts are owned by the sink even if it returns an error.
+
+    # Example
+
+    ```
+    use advanced_collections::counter::Counter;
+
+    fn main(){
+        let mut total: Counter<char> = Counter::new();
+        let mut c: Counter<char> = Counter::new();
+        c.extend("abb".chars());
+        c.flush_into(&mut total).unwrap();
+        c.extend("bc".chars());
+        c.flush_into(&mut total).unwrap();
+        assert!(c.is_empty());
+        assert_eq!(total.count(&'b'), 3);
+    }
+    ```
+    */
+    pub fn flush_into<K>(&mut self, sink: &mut K) -> Result<(), K::Error>
+    where
+        K: CounterSink<T, S>,
+        S: Clone,
+    {
+        let empty = HashMap::with_hasher(self.counter.hasher().clone());
+        let counts = ::std::mem::replace(&mut self.counter, empty);
+        sink.flush(Counter::from_hashmap(counts))
+    }
+}
+
+/**
+An iterator over the most common elements of a `Counter` together with their fractions of the total count.
+
+Created by `Counter::most_common_into_iter_with_total()`.
+*/
+pub struct MostCommonWithTotal<T> {
+    iter: VecIntoIter<(T, usize)>,
+    total: usize
 }
 
+impl<T> MostCommonWithTotal<T> {
+    ///Returns the sum of counts of all elements, fixed when the iterator was created.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl<T> Iterator for MostCommonWithTotal<T> {
+    type Item = (T, usize, f64);
+
+    fn next(&mut self) -> Option<<Self as Iterator>::Item> {
+        let (key, count) = self.iter.next()?;
+        Some((key, count, count as f64 / self.total as f64))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for MostCommonWithTotal<T> {}
+
 impl<T, S> Default for Counter<T, S>
 where
     T: Hash + Eq,
@@ -334,6 +1140,43 @@ where
     }
 }
 
+/*
+Multiplies the count by a non-negative factor, rounding the exact product down.
+Converting large counts to f64 would round them to 53 significant bits first,
+so the factor is split into its integer mantissa and binary exponent instead.
+Results that do not fit into usize are saturated.
+*/
+fn scale_down(count: usize, factor: f64) -> usize {
+    if count == 0 || factor == 0.0 {
+        return 0;
+    }
+    if factor.is_infinite() {
+        return usize::MAX;
+    }
+    let bits = factor.to_bits();
+    let exp_bits = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exp) = if exp_bits == 0 {
+        //subnormal numbers do not have the implicit leading bit
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), exp_bits - 1075)
+    };
+    //at most 64 + 53 bits
+    let product = count as u128 * mantissa as u128;
+    let scaled = if exp >= 0 {
+        if exp as u32 >= product.leading_zeros() {
+            return usize::MAX;
+        }
+        product << exp
+    } else if -exp >= 128 {
+        0
+    } else {
+        product >> -exp
+    };
+    if scaled > usize::MAX as u128 { usize::MAX } else { scaled as usize }
+}
+
 impl<T, S> Deref for Counter<T, S>
 where
     T: Hash + Eq,
@@ -542,6 +1385,24 @@ mod tests {
         let _cnt: Counter<i32> = Counter::new();
     }
 
+    #[test]
+    fn diff() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+        let a: Counter<i32> = Counter::from_iter(vec![1, 1, 2, 3, 3, 3]);
+        let b: Counter<i32, BuildHasherDefault<DefaultHasher>> = Counter::from_iter(vec![1, 3, 3, 3, 4, 4]);
+        let diff = a.diff(&b);
+        assert_eq!(diff.only_in_self(), &[(&2, 1)]);
+        assert_eq!(diff.only_in_other(), &[(&4, 2)]);
+        assert_eq!(diff.changed(), &[(&1, 2, 1)]);
+        assert_eq!(format!("{}", diff), "- 2: 1\n+ 4: 2\n~ 1: 2 -> 1\n");
+        let empty: Counter<i32> = Counter::new();
+        assert_eq!(empty.diff(&empty).to_string(), "");
+        assert!(a.approx_eq(&b, 2));
+        assert!(!a.approx_eq(&b, 1));
+        assert!(empty.approx_eq(&empty, 0));
+    }
+
     #[test]
     fn chars() {
         let s = "Lorem ipsum";
@@ -562,5 +1423,255 @@ mod tests {
 
     }
 
+    #[test]
+    fn merge_all() {
+        let a: Counter<i32> = Counter::from_iter(&[1, 2, 2]);
+        let b: Counter<i32> = Counter::from_iter(&[2, 3, 3, 3, 4]);
+        let c: Counter<i32> = Counter::new();
+        let m = Counter::merge_all(vec![a, b, c]);
+        assert_eq!(m.len(), 4);
+        assert_eq!(m[&1], 1);
+        assert_eq!(m[&2], 3);
+        assert_eq!(m[&3], 3);
+        assert_eq!(m[&4], 1);
+
+        let empty: Counter<i32> = Counter::merge_all(Vec::new());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut cnt: Counter<String> = Counter::new();
+        for word in "a b a c a b".split(' ') {
+            cnt.push_borrowed(word);
+        }
+        assert_eq!(cnt.count("a"), 3);
+        assert_eq!(cnt.count("b"), 2);
+        assert_eq!(cnt.count("d"), 0);
+        assert_eq!(cnt.remove("b"), Some(2));
+        assert_eq!(cnt.remove("b"), None);
+        assert_eq!(cnt.len(), 2);
+    }
+
+    #[test]
+    fn most_common_with_total() {
+        let cnt: Counter<i32> = Counter::from_iter(&[1, 2, 2, 3, 3, 3, 3, 3]);
+        let it = cnt.most_common_into_iter_with_total();
+        assert_eq!(it.total(), 8);
+        assert_eq!(it.len(), 3);
+        let v: Vec<(i32, usize, f64)> = it.collect();
+        assert_eq!(v, vec![(3, 5, 0.625), (2, 2, 0.25), (1, 1, 0.125)]);
+
+        let empty: Counter<i32> = Counter::new();
+        let mut it = empty.most_common_into_iter_with_total();
+        assert_eq!(it.total(), 0);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn decay() {
+        let mut cnt: Counter<i32> = Counter::from_iter(&[1, 1, 1, 2, 2, 3]);
+        cnt.decay(1.0, 0);
+        assert_eq!(cnt.len(), 3);
+        cnt.decay(0.7, 0);
+        assert_eq!(cnt.count(&1), 2);
+        assert_eq!(cnt.count(&2), 1);
+        //zero counts are always removed
+        assert!(!cnt.contains_key(&3));
+        cnt.decay(3.0, 4);
+        assert_eq!(cnt.count(&1), 6);
+        assert!(!cnt.contains_key(&2));
+    }
+
+    #[test]
+    fn decay_large_counts() {
+        let large = (1usize << 53) + 3;
+        let mut cnt: Counter<i32> = Counter::new();
+        cnt.insert(1, large);
+        cnt.insert(2, usize::MAX);
+        cnt.insert(3, 1);
+        cnt.decay(1.0, 0);
+        assert_eq!(cnt.count(&1), large);
+        assert_eq!(cnt.count(&2), usize::MAX);
+        cnt.decay(0.5, 0);
+        assert_eq!(cnt.count(&1), large / 2);
+        assert_eq!(cnt.count(&2), usize::MAX / 2);
+        assert!(!cnt.contains_key(&3));
+        cnt.decay(4.0, 0);
+        assert_eq!(cnt.count(&1), large / 2 * 4);
+        assert_eq!(cnt.count(&2), usize::MAX);
+        cnt.decay(f64::INFINITY, 0);
+        assert_eq!(cnt.count(&1), usize::MAX);
+        cnt.decay(f64::MIN_POSITIVE, 0);
+        assert!(cnt.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn decay_nan() {
+        let mut cnt: Counter<i32> = Counter::from_iter(&[1]);
+        cnt.decay(f64::NAN, 0);
+    }
+
+    #[test]
+    fn invert() {
+        let cnt: Counter<i32> = Counter::from_iter(&[1, 2, 2, 3, 3, 4, 4, 4]);
+        let mut inv = cnt.invert();
+        assert_eq!(inv.len(), 3);
+        inv.get_mut(&2).unwrap().sort();
+        assert_eq!(inv[&2], vec![2, 3]);
+        assert_eq!(inv[&3], vec![4]);
+        let btree = cnt.invert_into_btree();
+        assert_eq!(btree.keys().cloned().collect::<Vec<usize>>(), vec![1, 2, 3]);
+        assert_eq!(btree[&1], vec![1]);
+        assert!(Counter::<i32>::new().invert().is_empty());
+    }
+
+    #[test]
+    fn flush_into() {
+        struct Failing;
+        impl CounterSink<i32> for Failing {
+            type Error = usize;
+            fn flush(&mut self, counts: Counter<i32>) -> Result<(), usize> {
+                Err(counts.len())
+            }
+        }
+
+        let mut cnt: Counter<i32> = Counter::from_iter(&[1, 2, 2]);
+        let mut total: Counter<i32> = Counter::new();
+        cnt.flush_into(&mut total).unwrap();
+        assert!(cnt.is_empty());
+        cnt.push(2);
+        cnt.flush_into(&mut total).unwrap();
+        assert_eq!(total.count(&2), 3);
+        cnt.push(3);
+        assert_eq!(cnt.flush_into(&mut Failing), Err(1));
+        assert!(cnt.is_empty());
+    }
+
+    #[test]
+    fn take_replace() {
+        let mut cnt: Counter<i32> = Counter::from_iter(&[1, 1, 2]);
+        assert_eq!(cnt.take(&1), Some(2));
+        assert_eq!(cnt.take(&1), None);
+        assert_eq!(cnt.replace(2, 4), Some(1));
+        assert_eq!(cnt.count(&2), 4);
+        assert_eq!(cnt.replace(3, 0), None);
+        assert!(!cnt.contains_key(&3));
+        assert_eq!(cnt.replace(2, 0), Some(4));
+        assert!(cnt.is_empty());
+    }
+
+    #[test]
+    fn most_common_ref() {
+        let cnt: Counter<i32> = Counter::from_iter(&[1, 2, 2, 3, 3, 3, 4, 4, 4, 4]);
+        assert_eq!(cnt.most_common_ref(), vec![(&4, 4), (&3, 3), (&2, 2), (&1, 1)]);
+        assert_eq!(cnt.most_common_ref_n(3), vec![(&4, 4), (&3, 3), (&2, 2)]);
+        assert_eq!(cnt.most_common_ref_n(1), vec![(&4, 4)]);
+        assert!(cnt.most_common_ref_n(0).is_empty());
+        assert_eq!(cnt.most_common_ref_n(10).len(), 4);
+    }
+
+    #[test]
+    fn counts_vec() {
+        let cnt: Counter<i32> = Counter::from_iter(&[1, 2, 2, 3, 3, 3]);
+        let (keys, counts) = cnt.counts_vec();
+        assert_eq!(keys.len(), 3);
+        for (key, count) in keys.iter().zip(counts.iter()) {
+            assert_eq!(cnt[*key], *count);
+        }
+        let empty: Counter<i32> = Counter::new();
+        assert_eq!(empty.counts_vec(), (Vec::new(), Vec::new()));
+    }
 
-}
\ No newline at end of file
+    #[test]
+    fn rehash() {
+        use std::collections::hash_map::RandomState;
+        use fnv::FnvBuildHasher;
+        let cnt: Counter<char> = Counter::from_iter("abracadabra".chars());
+        let fast: Counter<char, FnvBuildHasher> = cnt.clone().rehash();
+        assert_eq!(fast.len(), 5);
+        assert_eq!(fast[&'a'], 5);
+        let back: Counter<char, RandomState> = fast.rehash_with_hasher(cnt.hasher().clone());
+        assert!(*back == *cnt);
+    }
+
+    #[test]
+    fn get_many() {
+        let cnt: Counter<char> = Counter::from_iter("abracadabra".chars());
+        assert_eq!(cnt.get_many(&['a', 'z', 'r', 'a']), vec![5, 0, 2, 5]);
+        assert!(cnt.get_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn count_or_insert_with() {
+        let mut cnt: Counter<String> = Counter::new();
+        let mut created = 0;
+        for word in ["a", "b", "a", "a"] {
+            *cnt.count_or_insert_with(word, || {
+                created += 1;
+                word.to_string()
+            }) += 1;
+        }
+        assert_eq!(created, 2);
+        assert_eq!(cnt.count("a"), 3);
+        assert_eq!(*cnt.count_or_insert_with("c", || "c".to_string()), 0);
+        assert_eq!(cnt.len(), 3);
+    }
+
+    #[test]
+    fn most_common_n() {
+        let cnt: Counter<char> = Counter::from_iter("aaaaabbbbcccdde".chars());
+        assert_eq!(cnt.most_common_n(3), vec![('a', 5), ('b', 4), ('c', 3)]);
+        assert_eq!(cnt.most_common_n(0), vec![]);
+        assert_eq!(cnt.most_common_n(10).len(), 5);
+        assert_eq!(cnt.most_common_n(10)[4], ('e', 1));
+        let empty: Counter<char> = Counter::new();
+        assert!(empty.most_common_n(3).is_empty());
+    }
+
+    #[test]
+    fn to_label_value_vecs() {
+        let cnt: Counter<char> = Counter::from_iter("aaaaabbbbcccdde".chars());
+        let (labels, values) = cnt.to_label_value_vecs(2);
+        assert_eq!(labels, vec![&'a', &'b']);
+        assert_eq!(values, vec![5, 4]);
+        let (labels, values) = cnt.to_label_value_vecs(0);
+        assert!(labels.is_empty() && values.is_empty());
+        assert_eq!(cnt.to_label_value_vecs(10).1, vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn display_bars() {
+        let cnt: Counter<&str> = Counter::from_iter(vec!["apple", "fig", "fig", "fig", "fig", "kiwi", "kiwi"]);
+        assert_eq!(cnt.display_bars(5, 4), "fig   | #### 4\nkiwi  | ## 2\napple | # 1\n");
+        assert_eq!(cnt.display_bars(1, 0), "fig |  4\n");
+        assert_eq!(cnt.display_bars(0, 10), "");
+        let mut zero: Counter<&str> = Counter::new();
+        zero.insert("a", 0);
+        assert_eq!(zero.display_bars(1, 3), "a |  0\n");
+        let mut huge: Counter<&str> = Counter::new();
+        huge.insert("a", usize::MAX);
+        huge.insert("b", usize::MAX / 2);
+        let expected = format!("a | ### {}\nb | # {}\n", usize::MAX, usize::MAX / 2);
+        assert_eq!(huge.display_bars(2, 3), expected);
+    }
+
+    #[test]
+    fn group_by_sets() {
+        let cnt: Counter<u32> = Counter::from_iter(vec![1, 1, 2, 3, 3, 3, 4]);
+        let mut sets: DisjointSet<u32> = DisjointSet::new();
+        sets.union(1, 3);
+        sets.union(5, 3);
+        sets.make_set(2);
+        let grouped = cnt.group_by_sets(&mut sets);
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(grouped.values().sum::<usize>(), 7);
+        let rep = grouped.keys().find(|k| [1, 3, 5].contains(*k)).cloned().unwrap();
+        assert_eq!(grouped[&rep], 5);
+        assert_eq!(grouped[&2], 1);
+        assert_eq!(grouped[&4], 1);
+        let empty: Counter<u32> = Counter::new();
+        assert!(empty.group_by_sets(&mut sets).is_empty());
+    }
+}
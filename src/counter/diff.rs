@@ -0,0 +1,84 @@
+use std::fmt;
+
+/**
+Differences between two counters, created by `Counter::diff()`.
+
+Elements are listed in an arbitrary order, but the `Display` implementation sorts lines
+by the printed elements, so the output is stable and can be compared in tests.
+
+# Example
+
+```
+use advanced_collections::counter::Counter;
+use std::iter::FromIterator;
+
+fn main(){
+    let expected: Counter<char> = Counter::from_iter("aabbc".chars());
+    let actual: Counter<char> = Counter::from_iter("abbbd".chars());
+    let diff = expected.diff(&actual);
+    assert_eq!(diff.only_in_self(), &[(&'c', 1)]);
+    assert_eq!(diff.to_string(), "- c: 1\n+ d: 1\n~ a: 2 -> 1\n~ b: 2 -> 3\n");
+}
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterDiff<'a, T> {
+    only_in_self: Vec<(&'a T, usize)>,
+    only_in_other: Vec<(&'a T, usize)>,
+    changed: Vec<(&'a T, usize, usize)>
+}
+
+impl<'a, T> CounterDiff<'a, T> {
+    pub(super) fn new(only_in_self: Vec<(&'a T, usize)>, only_in_other: Vec<(&'a T, usize)>, changed: Vec<(&'a T, usize, usize)>) -> Self {
+        Self {
+            only_in_self,
+            only_in_other,
+            changed
+        }
+    }
+
+    ///Returns elements counted only by the first counter, with their counts.
+    pub fn only_in_self(&self) -> &[(&'a T, usize)] {
+        &self.only_in_self
+    }
+
+    ///Returns elements counted only by the second counter, with their counts.
+    pub fn only_in_other(&self) -> &[(&'a T, usize)] {
+        &self.only_in_other
+    }
+
+    ///Returns elements counted by both counters a different number of times, with both counts.
+    pub fn changed(&self) -> &[(&'a T, usize, usize)] {
+        &self.changed
+    }
+
+    ///Checks if both counters are equal.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty() && self.changed.is_empty()
+    }
+}
+
+/**
+Prints one line per difference: `- x: 1` for elements only in the first counter,
+`+ x: 1` for elements only in the second one and `~ x: 1 -> 2` for changed counts.
+*/
+impl<'a, T> fmt::Display for CounterDiff<'a, T> where T: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut removed: Vec<(String, usize)> = self.only_in_self.iter().map(|(k, c)| (k.to_string(), *c)).collect();
+        let mut added: Vec<(String, usize)> = self.only_in_other.iter().map(|(k, c)| (k.to_string(), *c)).collect();
+        let mut changed: Vec<(String, usize, usize)> = self.changed.iter().map(|(k, a, b)| (k.to_string(), *a, *b)).collect();
+        removed.sort();
+        added.sort();
+        changed.sort();
+        for (key, count) in removed.iter() {
+            writeln!(f, "- {}: {}", key, count)?;
+        }
+        for (key, count) in added.iter() {
+            writeln!(f, "+ {}: {}", key, count)?;
+        }
+        for (key, a, b) in changed.iter() {
+            writeln!(f, "~ {}: {} -> {}", key, a, b)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::convert::TryFrom;
+use std::hash::BuildHasher;
+use std::iter::{Extend, FromIterator};
+use super::counter::Counter;
+
+//marks the end of a chain of keys with colliding hashes
+const NO_KEY: u32 = u32::MAX;
+
+/**
+A counter of strings that stores all distinct keys in a single text buffer.
+
+`Counter<String>` allocates a separate `String` for every distinct key.
+`InternedCounter` appends keys to one shared buffer instead and refers to them by their positions,
+which saves the allocation overhead and the `String` header of every key.
+Keys are pushed and queried as `&str`, so counting occurrences of already known keys never allocates.
+
+Keys cannot be removed, the buffer only grows. Use `to_counter()` to get a regular `Counter`
+when keys need to be modified.
+
+# Example
+
+```
+use advanced_collections::counter::InternedCounter;
+
+fn main(){
+    let log = "GET /index.html\nGET /index.html\nPOST /login\nGET /index.html";
+    let mut c: InternedCounter = InternedCounter::new();
+    c.extend(log.lines());
+    assert_eq!(c.len(), 2);
+    assert_eq!(c.count("GET /index.html"), 3);
+    assert_eq!(c.count("GET /"), 0);
+    assert_eq!(c.most_common()[0], ("GET /index.html", 3));
+    assert_eq!(c.text_len(), "GET /index.html".len() + "POST /login".len());
+}
+```
+*/
+#[derive(Clone, Debug)]
+pub struct InternedCounter<S = RandomState> {
+    //all distinct keys, concatenated
+    text: String,
+    //end of each key in the text, the key starts where the previous one ends
+    ends: Vec<usize>,
+    counts: Vec<usize>,
+    //first key with the given hash
+    heads: HashMap<u64, u32>,
+    //next key with the same hash as the given one
+    next: Vec<u32>,
+    hash_builder: S
+}
+
+impl InternedCounter<RandomState> {
+    ///Creates a new, empty counter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> InternedCounter<S> where S: BuildHasher {
+    ///Creates an empty counter which will use the given hash builder to hash keys.
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            text: String::new(),
+            ends: Vec::new(),
+            counts: Vec::new(),
+            heads: HashMap::new(),
+            next: Vec::new(),
+            hash_builder
+        }
+    }
+
+    ///Returns the number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    ///Checks if the counter is empty.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    ///Returns the total length in bytes of all distinct keys stored in the buffer.
+    pub fn text_len(&self) -> usize {
+        self.text.len()
+    }
+
+    ///Counts one occurrence of the key.
+    pub fn push(&mut self, key: &str) {
+        self.push_n(key, 1);
+    }
+
+    ///Counts `n` occurrences of the key. Nothing is stored if `n` is 0.
+    pub fn push_n(&mut self, key: &str, n: usize) {
+        if n == 0 {
+            return;
+        }
+        let hash = self.hash_builder.hash_one(key);
+        match self.find(hash, key) {
+            Some(id) => self.counts[id as usize] += n,
+            None => {
+                let id = u32::try_from(self.counts.len()).ok()
+                    .filter(|&id| id != NO_KEY)
+                    .expect("Too many distinct keys.");
+                self.text.push_str(key);
+                self.ends.push(self.text.len());
+                self.counts.push(n);
+                let head = self.heads.entry(hash).or_insert(NO_KEY);
+                self.next.push(*head);
+                *head = id;
+            }
+        }
+    }
+
+    ///Returns the number of occurrences of the key, 0 if it was never pushed.
+    pub fn count(&self, key: &str) -> usize {
+        let hash = self.hash_builder.hash_one(key);
+        self.find(hash, key).map_or(0, |id| self.counts[id as usize])
+    }
+
+    ///Returns an iterator over keys and their counts, in the order in which keys were first pushed.
+    pub fn iter(&self) -> impl Iterator<Item=(&str, usize)> + '_ {
+        (0..self.counts.len()).map(move |id| (self.key(id), self.counts[id]))
+    }
+
+    /**
+    Returns keys and their counts, starting with the most common ones.
+
+    Keys with equal counts are ordered by their first occurrence.
+    */
+    pub fn most_common(&self) -> Vec<(&str, usize)> {
+        let mut res: Vec<(&str, usize)> = self.iter().collect();
+        res.sort_by_key(|&(_, count)| Reverse(count));
+        res
+    }
+
+    ///Removes all keys, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.ends.clear();
+        self.counts.clear();
+        self.heads.clear();
+        self.next.clear();
+    }
+
+    ///Creates a regular `Counter` with a separate `String` for every key.
+    pub fn to_counter<S2>(&self) -> Counter<String, S2> where S2: BuildHasher + Default {
+        let mut res = Counter::with_capacity(self.len());
+        for (key, count) in self.iter() {
+            res.insert(key.to_string(), count);
+        }
+        res
+    }
+
+    fn key(&self, id: usize) -> &str {
+        let start = if id == 0 { 0 } else { self.ends[id - 1] };
+        &self.text[start..self.ends[id]]
+    }
+
+    fn find(&self, hash: u64, key: &str) -> Option<u32> {
+        let mut id = *self.heads.get(&hash)?;
+        while id != NO_KEY {
+            if self.key(id as usize) == key {
+                return Some(id);
+            }
+            id = self.next[id as usize];
+        }
+        None
+    }
+}
+
+impl<S> Default for InternedCounter<S> where S: BuildHasher + Default {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<'a, S> Extend<&'a str> for InternedCounter<S> where S: BuildHasher {
+    fn extend<I: IntoIterator<Item=&'a str>>(&mut self, iter: I) {
+        for key in iter {
+            self.push(key);
+        }
+    }
+}
+
+impl<'a, S> FromIterator<&'a str> for InternedCounter<S> where S: BuildHasher + Default {
+    fn from_iter<I: IntoIterator<Item=&'a str>>(iter: I) -> Self {
+        let mut res = Self::default();
+        res.extend(iter);
+        res
+    }
+}
+
+impl<S> PartialEq for InternedCounter<S> where S: BuildHasher {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, count)| other.count(key) == count)
+    }
+}
+
+impl<S> Eq for InternedCounter<S> where S: BuildHasher {}
+
+impl<'a, S, S2> From<&'a Counter<String, S2>> for InternedCounter<S>
+where
+    S: BuildHasher + Default,
+    S2: BuildHasher,
+{
+    fn from(counter: &'a Counter<String, S2>) -> Self {
+        let mut res = Self::default();
+        for (key, &count) in counter.iter() {
+            res.push_n(key, count);
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::Hasher;
+
+    //makes all keys collide
+    #[derive(Default, Clone)]
+    struct ConstHasher;
+
+    impl Hasher for ConstHasher {
+        fn finish(&self) -> u64 {
+            7
+        }
+
+        fn write(&mut self, _bytes: &[u8]) {}
+    }
+
+    impl BuildHasher for ConstHasher {
+        type Hasher = ConstHasher;
+
+        fn build_hasher(&self) -> ConstHasher {
+            ConstHasher
+        }
+    }
+
+    #[test]
+    fn test_matches_counter(){
+        let words: Vec<String> = (0..500).map(|i| format!("key-with-a-long-prefix-{}", i % 37)).collect();
+        let expected: Counter<String> = words.iter().cloned().collect();
+        let interned: InternedCounter = words.iter().map(|s| s.as_str()).collect();
+        let colliding: InternedCounter<ConstHasher> = words.iter().map(|s| s.as_str()).collect();
+        for c in [interned.to_counter::<RandomState>(), colliding.to_counter()].iter() {
+            assert!(c.diff(&expected).is_empty());
+        }
+        assert_eq!(interned.len(), 37);
+        assert_eq!(colliding.count("key-with-a-long-prefix-5"), expected.count("key-with-a-long-prefix-5"));
+        assert_eq!(colliding.count("key-with-a-long-prefix-"), 0);
+        assert_eq!(InternedCounter::<RandomState>::from(&expected), interned);
+    }
+
+    #[test]
+    fn test_push_n_and_clear(){
+        let mut c: InternedCounter = InternedCounter::new();
+        c.push_n("a", 0);
+        assert!(c.is_empty());
+        c.push_n("a", 2);
+        c.push("");
+        c.push("ab");
+        c.push("ab");
+        c.push("ab");
+        assert_eq!(c.most_common(), vec![("ab", 3), ("a", 2), ("", 1)]);
+        assert_eq!(c.iter().collect::<Vec<_>>(), vec![("a", 2), ("", 1), ("ab", 3)]);
+        c.clear();
+        assert_eq!(c.count("a"), 0);
+        assert_eq!(c.text_len(), 0);
+    }
+}
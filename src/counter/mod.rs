@@ -25,7 +25,13 @@ This structure was highly inspired by the Python `Counter` class:
 */
 
 mod counter;
+mod diff;
 mod fast_counter;
-
-pub use self::counter::Counter;
-pub use self::fast_counter::FastCounter;
\ No newline at end of file
+mod interned;
+mod sink;
+
+pub use self::counter::{Counter, MostCommonWithTotal};
+pub use self::diff::CounterDiff;
+pub use self::fast_counter::FastCounter;
+pub use self::interned::InternedCounter;
+pub use self::sink::CounterSink;
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::collections::hash_map::RandomState;
+use std::convert::Infallible;
+use std::hash::{BuildHasher, Hash};
+use super::counter::Counter;
+
+/**
+A destination for counts periodically flushed from a `Counter`.
+
+Implement it for a database client, metrics exporter or any other aggregation target
+and use `Counter::flush_into()` to hand over counts collected since the last flush.
+
+# Example
+
+```
+use advanced_collections::counter::{Counter, CounterSink};
+
+struct Totals {
+    flushes: usize,
+    total: usize
+}
+
+impl CounterSink<&'static str> for Totals {
+    type Error = ();
+
+    fn flush(&mut self, counts: Counter<&'static str>) -> Result<(), ()> {
+        self.flushes += 1;
+        self.total += counts.values().sum::<usize>();
+        Ok(())
+    }
+}
+
+fn main(){
+    let mut sink = Totals{flushes: 0, total: 0};
+    let mut c: Counter<&'static str> = Counter::new();
+    c.push("GET");
+    c.push("POST");
+    c.flush_into(&mut sink).unwrap();
+    assert!(c.is_empty());
+    c.push("GET");
+    c.flush_into(&mut sink).unwrap();
+    assert_eq!(sink.flushes, 2);
+    assert_eq!(sink.total, 3);
+}
+```
+*/
+pub trait CounterSink<T, S = RandomState>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    ///Error returned when the sink fails to accept counts.
+    type Error;
+
+    ///Consumes counts flushed from a counter.
+    fn flush(&mut self, counts: Counter<T, S>) -> Result<(), Self::Error>;
+}
+
+///Another counter can be used as a sink, accumulating all flushed counts.
+impl<T, S1, S2> CounterSink<T, S1> for Counter<T, S2>
+where
+    T: Hash + Eq,
+    S1: BuildHasher,
+    S2: BuildHasher,
+{
+    type Error = Infallible;
+
+    fn flush(&mut self, counts: Counter<T, S1>) -> Result<(), Self::Error> {
+        *self += counts;
+        Ok(())
+    }
+}
@@ -2,10 +2,14 @@ use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::collections::hash_map::RandomState;
 use std::hash::{BuildHasher, Hash};
+use std::borrow::Borrow;
 use std::iter::{Extend, FromIterator};
 use std::default::Default;
 use std::iter::Iterator;
 use std::collections::hash_map::IntoIter;
+use std::collections::TryReserveError;
+use fnv::FnvBuildHasher;
+use crate::interval::Interval;
 
 #[derive(Debug, Clone, Copy)]
 struct Data {
@@ -104,7 +108,10 @@ fn main(){
 #[derive(Clone, Debug)]
 pub struct DisjointSet<T, S=RandomState>  where T: Eq+Hash , S: BuildHasher{
     ids: HashMap<T, usize, S>,
-    data_by_id: Vec<Data>
+    data_by_id: Vec<Data>,
+    //used by union_interval(), maps an element to a greater one known to be in the same subset
+    //together with all elements between them
+    reach: HashMap<usize, usize, FnvBuildHasher>
 }
 
 impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
@@ -123,7 +130,8 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     pub fn with_capacity(capacity: usize) -> Self where S: Default{
         Self {
             ids: HashMap::with_capacity_and_hasher(capacity, Default::default()),
-            data_by_id: Vec::with_capacity(capacity)
+            data_by_id: Vec::with_capacity(capacity),
+            reach: HashMap::default()
         }
     }
 
@@ -135,7 +143,8 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     pub fn with_hasher(hash_builder: S) -> Self {
         Self {
             ids: HashMap::with_hasher(hash_builder),
-            data_by_id: Vec::new()
+            data_by_id: Vec::new(),
+            reach: HashMap::default()
         }
     }
 
@@ -148,7 +157,8 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
         Self {
             ids: HashMap::with_capacity_and_hasher(capacity, hash_builder),
-            data_by_id: Vec::with_capacity(capacity)
+            data_by_id: Vec::with_capacity(capacity),
+            reach: HashMap::default()
         }
     }
 
@@ -174,6 +184,68 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     pub fn union(&mut self, a :T, b: T) {
         let a = self.make_or_get_set(a);
         let b = self.make_or_get_set(b);
+        self.union_ids(a, b);
+    }
+
+    /**
+    Joins all provided elements into one subset.
+
+    Elements that do not exist in the collection are added first.
+    Each element is joined with the first one, so the group is processed in a single pass.
+
+    **Complexity:** O(k α(n)) ≈ O(k), where k is the size of the group
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<&str> = DisjointSet::new();
+        //rows of pre-grouped identifiers
+        ds.union_group(vec!["a", "b", "c"]);
+        ds.union_group(vec!["d", "e"]);
+        ds.union_group(vec!["c", "e"]);
+        assert!(ds.in_union(&"a", &"d"));
+        assert_eq!(ds.len(), 5);
+    }
+    ```
+    */
+    pub fn union_group<I>(&mut self, group: I) where I: IntoIterator<Item=T> {
+        let mut group = group.into_iter();
+        let first = match group.next() {
+            None => return,
+            Some(val) => self.make_or_get_set(val)
+        };
+        for val in group {
+            let id = self.make_or_get_set(val);
+            self.union_ids(first, id);
+        }
+    }
+
+    /**
+    Moves all elements of the other set into this one, preserving their subsets.
+
+    Elements that exist in both sets join subsets from both sets.
+    */
+    #[cfg(feature = "rayon")]
+    pub(super) fn merge(&mut self, mut other: Self) {
+        self.reserve(other.len());
+        let roots: Vec<usize> = (0..other.data_by_id.len())
+            .map(|id| Self::find_with_path_compression(&mut other.data_by_id, id))
+            .collect();
+        //id in this set of the first element of each subset of the other set
+        let mut representatives: Vec<Option<usize>> = vec![None; roots.len()];
+        for (val, id) in other.ids {
+            let new_id = self.make_or_get_set(val);
+            match representatives[roots[id]] {
+                None => representatives[roots[id]] = Some(new_id),
+                Some(rep) => self.union_ids(rep, new_id)
+            }
+        }
+    }
+
+    fn union_ids(&mut self, a: usize, b: usize) {
         let mut a_root = Self::find_with_path_compression(&mut self.data_by_id, a);
         let mut b_root = Self::find_with_path_compression(&mut self.data_by_id, b);
         if a_root == b_root {
@@ -196,18 +268,22 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     /**
     Check if the given element has been added to this collection.
 
+    The element may be any borrowed form of the element type, e.g. `&str` for `String` elements.
+
     **Complexity:** O(α(n)) ≈ O(1)
     */
-    pub fn contains(&self, val: &T) -> bool {
+    pub fn contains<Q>(&self, val: &Q) -> bool where T: Borrow<Q>, Q: ?Sized + Hash + Eq {
         self.ids.contains_key(val)
     }
 
     /**
     Checks if the given two elements are in the same subset.
 
+    Elements may be any borrowed form of the element type, e.g. `&str` for `String` elements.
+
     **Complexity:** O(α(n)) ≈ O(1)
     */
-    pub fn in_union(&mut self, a :&T, b: &T) -> bool{
+    pub fn in_union<Q>(&mut self, a :&Q, b: &Q) -> bool where T: Borrow<Q>, Q: ?Sized + Hash + Eq {
         let a = match self.ids.get(a) {
             Option::None => return false,
             Option::Some(id) => *id
@@ -221,6 +297,40 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
         Self::find_with_path_compression(&mut self.data_by_id, a) == Self::find_with_path_compression(&mut self.data_by_id, b)
     }
 
+    /**
+    Joins subsets of two elements that already exist in the collection.
+
+    Unlike `union()` it accepts borrowed forms of the element type, so no owned elements
+    need to be created for a lookup. Returns false and does nothing if any of the elements
+    does not exist.
+
+    **Complexity:** O(α(n)) ≈ O(1)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<String> = DisjointSet::new();
+        ds.make_set("a".to_string());
+        ds.make_set("b".to_string());
+        assert!(ds.union_existing("a", "b"));
+        assert!(!ds.union_existing("a", "c"));
+        assert!(ds.in_union("a", "b"));
+        assert!(!ds.contains("c"));
+    }
+    ```
+    */
+    pub fn union_existing<Q>(&mut self, a: &Q, b: &Q) -> bool where T: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        let (a, b) = match (self.ids.get(a), self.ids.get(b)) {
+            (Some(&a), Some(&b)) => (a, b),
+            _ => return false
+        };
+        self.union_ids(a, b);
+        true
+    }
+
     pub fn is_empty(&self) -> bool {
         self.ids.is_empty()
     }
@@ -231,7 +341,8 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
 
     pub fn clear(&mut self) {
         self.ids.clear();
-        self.data_by_id.clear()
+        self.data_by_id.clear();
+        self.reach.clear();
     }
 
     pub fn reserve(&mut self, additional: usize) {
@@ -239,6 +350,274 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
         self.ids.reserve(additional);
     }
 
+    /**
+    Returns the number of elements the collection can hold without reallocating.
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<u32> = DisjointSet::with_capacity(100);
+        assert!(ds.capacity() >= 100);
+        ds.union(1, 2);
+        ds.shrink_to_fit();
+        assert!(ds.capacity() >= 2);
+        assert!(ds.ids_capacity() >= ds.capacity());
+    }
+    ```
+    */
+    pub fn capacity(&self) -> usize {
+        self.data_by_id.capacity().min(self.ids.capacity())
+    }
+
+    ///Returns the number of elements the internal element-to-id map can hold without reallocating.
+    pub fn ids_capacity(&self) -> usize {
+        self.ids.capacity()
+    }
+
+    ///Shrinks the capacity of the collection as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.data_by_id.shrink_to_fit();
+        self.ids.shrink_to_fit();
+        self.reach.shrink_to_fit();
+    }
+
+    /**
+    Tries to reserve capacity for at least `additional` more elements.
+
+    Returns an error instead of aborting if the capacity overflows or the allocation fails.
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<u32> = DisjointSet::new();
+        assert!(ds.try_reserve(100).is_ok());
+        assert!(ds.try_reserve(usize::MAX).is_err());
+    }
+    ```
+    */
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.data_by_id.try_reserve(additional)?;
+        self.ids.try_reserve(additional)
+    }
+
+    /**
+    Assigns consecutive labels `0..k` to subsets, where k is the number of subsets.
+
+    The returned vector contains labels of all elements in the order in which
+    elements were added to the collection. Subsets are labeled in the order
+    of their first elements, so the first element always gets the label 0.
+
+    **Complexity:** O(n)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::FastDisjointSet;
+
+    fn main(){
+        //pixels of a 1-dimensional image
+        let mut ds: FastDisjointSet<u32> = FastDisjointSet::default();
+        for pixel in 0..6 {
+            ds.make_set(pixel);
+        }
+        ds.union(0, 1);
+        ds.union(3, 5);
+        ds.union(1, 2);
+        assert_eq!(ds.labels(), vec![0, 0, 0, 1, 2, 1]);
+    }
+    ```
+    */
+    pub fn labels(&mut self) -> Vec<u32> {
+        let mut label_by_root: Vec<Option<u32>> = vec![None; self.data_by_id.len()];
+        let mut next_label = 0;
+        let mut labels = Vec::with_capacity(self.data_by_id.len());
+        for id in 0..self.data_by_id.len() {
+            let root = Self::find_with_path_compression(&mut self.data_by_id, id);
+            let label = *label_by_root[root].get_or_insert_with(|| {
+                next_label += 1;
+                next_label - 1
+            });
+            labels.push(label);
+        }
+        labels
+    }
+
+    /**
+    Assigns consecutive ids `0..k` to subsets and returns the id of every element.
+
+    Ids are the same as labels returned by `labels()`, which provides them as a vector
+    ordered by insertion of elements, without building a map.
+
+    **Complexity:** O(n)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<&str> = DisjointSet::new();
+        ds.union("a", "b");
+        ds.make_set("c");
+        ds.union("d", "b");
+        let ids = ds.relabel();
+        assert_eq!(ids[&"a"], 0);
+        assert_eq!(ids[&"d"], 0);
+        assert_eq!(ids[&"c"], 1);
+    }
+    ```
+    */
+    pub fn relabel(&mut self) -> HashMap<&T, u32> {
+        let labels = self.labels();
+        self.ids.iter().map(|(val, &id)| (val, labels[id])).collect()
+    }
+
+    /**
+    Exports the partition as a minimal list of pairs of elements that belong to the same subset.
+
+    Every element that is not the root of its subset is paired with the root.
+    Elements of single-element subsets are paired with themselves, so that they are not lost.
+    The partition can be recreated by `from_union_edges()` from the returned pairs,
+    regardless of the implementation and the internal state of this set.
+    Pairs are ordered by insertion of their first elements.
+
+    **Complexity:** O(n)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<&str> = DisjointSet::new();
+        ds.union("a", "b");
+        ds.union("b", "c");
+        ds.make_set("d");
+        let edges: Vec<(&str, &str)> = ds.to_union_edges().into_iter().map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(edges.len(), 3);
+
+        let mut copy: DisjointSet<&str> = DisjointSet::from_union_edges(edges);
+        assert_eq!(copy.len(), 4);
+        assert!(copy.in_union(&"a", &"c"));
+        assert!(!copy.in_union(&"a", &"d"));
+    }
+    ```
+    */
+    pub fn to_union_edges(&mut self) -> Vec<(&T, &T)> {
+        let roots: Vec<usize> = (0..self.data_by_id.len())
+            .map(|id| Self::find_with_path_compression(&mut self.data_by_id, id))
+            .collect();
+        let mut sizes = vec![0usize; roots.len()];
+        for &root in roots.iter() {
+            sizes[root] += 1;
+        }
+        let mut elements: Vec<Option<&T>> = vec![None; roots.len()];
+        for (val, &id) in self.ids.iter() {
+            elements[id] = Some(val);
+        }
+        let element = |id: usize| elements[id].expect("Every id belongs to an element");
+        roots.iter().enumerate()
+            .filter(|&(id, &root)| id != root || sizes[root] == 1)
+            .map(|(id, &root)| (element(id), element(root)))
+            .collect()
+    }
+
+    /**
+    Creates a set from pairs of elements that belong to the same subset.
+
+    Accepts pairs exported by `to_union_edges()`. A pair of an element with itself
+    creates a single-element subset.
+
+    **Complexity:** O(m α(n)), where m is the number of pairs
+    */
+    pub fn from_union_edges<I>(edges: I) -> Self where I: IntoIterator<Item=(T, T)>, S: Default {
+        let mut ds = Self::new();
+        for (a, b) in edges {
+            ds.union(a, b);
+        }
+        ds
+    }
+
+    /**
+    Writes the root of each element into the provided buffer without allocating.
+
+    Elements and roots are identified by the order in which elements were added
+    to the collection: `out[i]` receives the index of the root of the i-th element.
+    Two elements belong to the same subset if their roots are equal.
+    Panics if the buffer is shorter than the number of elements.
+
+    **Complexity:** O(n)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::FastDisjointSet;
+
+    fn main(){
+        let mut ds: FastDisjointSet<u32> = FastDisjointSet::default();
+        for pixel in 0..4 {
+            ds.make_set(pixel);
+        }
+        ds.union(1, 3);
+        let mut roots = [0u32; 4];
+        ds.compress_into(&mut roots);
+        assert_eq!(roots[1], roots[3]);
+        assert_ne!(roots[0], roots[1]);
+        assert_eq!(roots[2], 2);
+    }
+    ```
+    */
+    pub fn compress_into(&mut self, out: &mut [u32]) {
+        if out.len() < self.data_by_id.len() {
+            panic!("Buffer is shorter than the number of elements.");
+        }
+        for (id, root) in out.iter_mut().enumerate().take(self.data_by_id.len()) {
+            *root = Self::find_with_path_compression(&mut self.data_by_id, id) as u32;
+        }
+    }
+
+    /**
+    Returns an iterator over all elements paired with representatives of their subsets.
+
+    All elements of a subset share the same representative, which is also an element
+    of that subset. Elements are returned in an arbitrary order.
+
+    **Complexity:** O(n)
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::DisjointSet;
+
+    fn main(){
+        let mut ds: DisjointSet<&str> = DisjointSet::new();
+        ds.union("a", "b");
+        ds.make_set("c");
+        let rows: Vec<(&&str, &&str)> = ds.iter_with_roots().collect();
+        assert_eq!(rows.len(), 3);
+        let root_of = |x| rows.iter().find(|(el, _)| **el == x).unwrap().1;
+        assert_eq!(root_of("a"), root_of("b"));
+        assert_eq!(*root_of("c"), "c");
+    }
+    ```
+    */
+    pub fn iter_with_roots(&mut self) -> impl Iterator<Item=(&T, &T)> + '_ {
+        let roots: Vec<usize> = (0..self.data_by_id.len())
+            .map(|id| Self::find_with_path_compression(&mut self.data_by_id, id))
+            .collect();
+        let mut elements: Vec<Option<&T>> = vec![None; roots.len()];
+        for (val, &id) in self.ids.iter() {
+            elements[id] = Some(val);
+        }
+        self.ids.iter().map(move |(val, &id)| (val, elements[roots[id]].expect("Every id belongs to an element")))
+    }
+
     fn make_or_get_set(&mut self, val: T) -> usize{
         let next_id = self.ids.len();
         //insert but do not override existing one
@@ -272,12 +651,91 @@ impl<T, S> DisjointSet<T, S> where T:Eq + Hash , S:BuildHasher{
     }
 }
 
+impl<S> DisjointSet<usize, S> where S: BuildHasher {
+    /**
+    Joins all integers that belong to the interval into one subset.
+
+    Integers that do not exist in the collection are added first.
+    Neighbouring integers joined by previous calls are skipped without checking
+    each of them again, so overlapping intervals are processed in amortized time
+    proportional to the number of integers that were not joined with their neighbours yet.
+    This suits offline range-merging problems, such as painting segments
+    or coalescing calendar slots.
+
+    **Complexity:** O(k α(n)) amortized, where k is the number of newly joined neighbours
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::FastDisjointSet;
+    use advanced_collections::interval::Interval;
+
+    fn main(){
+        //booked slots of a calendar
+        let mut ds: FastDisjointSet<usize> = FastDisjointSet::default();
+        ds.union_interval(&Interval::closed(9, 11));
+        ds.union_interval(&Interval::lower_closed(14, 16));
+        ds.union_interval(&Interval::closed(10, 12));
+        assert_eq!(ds.len(), 6);
+        assert!(ds.in_union(&9, &12));
+        assert!(!ds.in_union(&12, &14));
+        assert!(!ds.contains(&16));
+    }
+    ```
+    */
+    pub fn union_interval(&mut self, range: &Interval<usize>) {
+        let (lo, up) = match (range.lower(), range.upper()) {
+            (Some(lo), Some(up)) => (lo, up),
+            _ => return
+        };
+        let first = if lo.is_closed() { *lo.val() } else { *lo.val() + 1 };
+        let last = if up.is_closed() { *up.val() } else { *up.val() - 1 };
+        if first > last {
+            //an interval like (2, 3) contains no integers
+            return;
+        }
+        self.make_or_get_set(first);
+        let mut current = first;
+        loop {
+            current = self.reach_from(current);
+            if current >= last {
+                break;
+            }
+            let a = self.make_or_get_set(current);
+            let b = self.make_or_get_set(current + 1);
+            self.union_ids(a, b);
+            self.reach.insert(current, current + 1);
+            current += 1;
+        }
+        if current > first {
+            self.reach.insert(first, current);
+        }
+    }
+
+    //finds the greatest element joined with all elements between it and the given one
+    fn reach_from(&mut self, val: usize) -> usize {
+        let mut end = val;
+        while let Some(&next) = self.reach.get(&end) {
+            end = next;
+        }
+        //path compression
+        let mut current = val;
+        while current != end {
+            let next = self.reach[&current];
+            self.reach.insert(current, end);
+            current = next;
+        }
+        end
+    }
+}
+
 impl<T, S> Default for DisjointSet<T, S>  where T: Eq+Hash , S: BuildHasher + Default {
     fn default() -> Self {
 
         Self{
             ids: HashMap::default(),
-            data_by_id: Vec::default()
+            data_by_id: Vec::default(),
+            reach: HashMap::default()
         }
     }
 }
@@ -433,4 +891,194 @@ mod tests {
 
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn borrowed_lookups(){
+        let mut ds: DisjointSet<String> = DisjointSet::from_iter(vec!["x".to_string(), "y".to_string(), "z".to_string()]);
+        assert!(ds.contains("x"));
+        assert!(!ds.contains("w"));
+        assert!(!ds.in_union("x", "y"));
+        assert!(ds.union_existing("x", "y"));
+        assert!(!ds.union_existing("y", "w"));
+        assert!(!ds.contains("w"));
+        assert!(ds.in_union("y", "x"));
+        assert!(!ds.in_union("x", "z"));
+        assert!(!ds.in_union("x", "w"));
+    }
+
+    #[test]
+    fn iter_with_roots(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        ds.union(1, 2);
+        ds.union(3, 4);
+        ds.union(2, 4);
+        ds.make_set(5);
+        let pairs: Vec<(i32, i32)> = ds.iter_with_roots().map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(pairs.len(), 5);
+        let root = pairs.iter().find(|p| p.0 == 1).unwrap().1;
+        assert!([1, 2, 3, 4].contains(&root));
+        for p in &pairs {
+            if p.0 == 5 {
+                assert_eq!(p.1, 5);
+            } else {
+                assert_eq!(p.1, root);
+            }
+        }
+        let empty: Vec<_> = DisjointSet::<i32>::new().iter_with_roots().map(|(a, b)| (*a, *b)).collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn capacity(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        assert_eq!(ds.capacity(), 0);
+        ds.reserve(1000);
+        assert!(ds.capacity() >= 1000);
+        ds.union(1, 2);
+        ds.union(3, 2);
+        ds.shrink_to_fit();
+        assert!(ds.capacity() >= 3 && ds.capacity() < 1000);
+        assert!(ds.in_union(&1, &3));
+    }
+
+    #[test]
+    fn union_group(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        ds.union_group(Vec::new());
+        assert!(ds.is_empty());
+        ds.union_group(vec![1]);
+        ds.union_group(vec![2, 3, 4]);
+        ds.union_group(vec![5, 6]);
+        assert!(ds.contains(&1));
+        assert!(ds.in_union(&2, &4));
+        assert!(!ds.in_union(&1, &2));
+        assert!(!ds.in_union(&4, &5));
+        ds.union_group(vec![6, 7, 3]);
+        assert!(ds.in_union(&2, &5));
+        assert!(ds.in_union(&7, &4));
+        assert_eq!(ds.len(), 7);
+    }
+
+    #[test]
+    fn labels(){
+        let mut ds: DisjointSet<char> = DisjointSet::new();
+        assert!(ds.labels().is_empty());
+        ds.union('a', 'b');
+        ds.union('c', 'd');
+        ds.make_set('e');
+        ds.union('d', 'b');
+        ds.union('f', 'e');
+        assert_eq!(ds.labels(), vec![0, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn compress_into(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        ds.union(10, 20);
+        ds.union(30, 40);
+        ds.union(40, 20);
+        ds.make_set(50);
+        let mut out = [u32::MAX; 6];
+        ds.compress_into(&mut out);
+        assert!(out[..4].iter().all(|&r| r == out[0]));
+        assert_eq!(out[4], 4);
+        //elements outside of the collection are not touched
+        assert_eq!(out[5], u32::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compress_into_short(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        ds.union(1, 2);
+        ds.compress_into(&mut [0; 1]);
+    }
+
+    #[test]
+    fn relabel(){
+        let mut ds: DisjointSet<i32> = DisjointSet::new();
+        assert!(ds.relabel().is_empty());
+        for x in 0..6 {
+            ds.make_set(x);
+        }
+        ds.union(4, 5);
+        ds.union(1, 3);
+        ds.union(3, 0);
+        let ids = ds.relabel();
+        assert_eq!(ids.len(), 6);
+        assert_eq!((ids[&0], ids[&1], ids[&3]), (0, 0, 0));
+        assert_eq!(ids[&2], 1);
+        assert_eq!((ids[&4], ids[&5]), (2, 2));
+    }
+
+    #[test]
+    fn union_edges(){
+        let mut ds: DisjointSet<u32> = DisjointSet::new();
+        assert!(ds.to_union_edges().is_empty());
+        for x in 0..10 {
+            ds.make_set(x);
+        }
+        ds.union_group(vec![0, 3, 6, 9]);
+        ds.union(1, 4);
+        ds.union(7, 4);
+        let edges: Vec<(u32, u32)> = ds.to_union_edges().into_iter().map(|(a, b)| (*a, *b)).collect();
+        //10 elements in 5 subsets: 5 edges for bigger subsets, 3 for single-element ones
+        assert_eq!(edges.len(), 5 + 3);
+        assert_eq!(edges.iter().filter(|(a, b)| a == b).count(), 3);
+        let mut copy: DisjointSet<u32> = DisjointSet::from_union_edges(edges);
+        assert_eq!(copy.len(), 10);
+        for x in 0..10 {
+            for y in 0..10 {
+                assert_eq!(ds.in_union(&x, &y), copy.in_union(&x, &y));
+            }
+        }
+    }
+
+    #[test]
+    fn union_interval(){
+        let mut ds: DisjointSet<usize> = DisjointSet::new();
+        ds.union_interval(&Interval::empty());
+        ds.union_interval(&Interval::open(2, 3));
+        assert!(ds.is_empty());
+        ds.union_interval(&Interval::closed(5, 5));
+        assert_eq!(ds.len(), 1);
+        ds.union_interval(&Interval::open(0, 4));
+        assert_eq!(ds.len(), 4);
+        assert!(ds.in_union(&1, &3));
+        assert!(!ds.in_union(&3, &5));
+        ds.union_interval(&Interval::closed(20, 30));
+        ds.union_interval(&Interval::closed(3, 21));
+        assert_eq!(ds.len(), 30);
+        for x in 1..=30 {
+            assert!(ds.in_union(&1, &x));
+        }
+        ds.union_interval(&Interval::closed(2, 25));
+        ds.union_interval(&Interval::closed(usize::MAX - 1, usize::MAX));
+        assert!(ds.in_union(&(usize::MAX - 1), &usize::MAX));
+        ds.clear();
+        ds.union_interval(&Interval::closed(0, 2));
+        assert_eq!(ds.len(), 3);
+    }
+
+    #[test]
+    fn union_interval_matches_union(){
+        use crate::test_util::XorShift;
+        let mut rng = XorShift::new(11);
+        let mut ds: DisjointSet<usize> = DisjointSet::new();
+        let mut expected: DisjointSet<usize> = DisjointSet::new();
+        for _ in 0..200 {
+            let lo = rng.below(300) as usize;
+            let up = lo + rng.below(20) as usize;
+            ds.union_interval(&Interval::closed(lo, up));
+            for x in lo..up {
+                expected.union(x, x + 1);
+            }
+            expected.make_set(up);
+        }
+        assert_eq!(ds.len(), expected.len());
+        for x in 0..320 {
+            assert_eq!(ds.contains(&x), expected.contains(&x));
+            assert_eq!(ds.in_union(&0, &x), expected.in_union(&0, &x), "{}", x);
+            assert_eq!(ds.in_union(&x, &(x + 1)), expected.in_union(&x, &(x + 1)), "{}", x);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::hash::BuildHasher;
+use std::default::Default;
+use super::disjoint_set::DisjointSet;
+
+const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const NEIGHBORS8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/*
+Helpers for connected-component labeling on 2D grids (images).
+Cells are identified by (x, y) coordinates.
+*/
+impl<S> DisjointSet<(usize, usize), S> where S: BuildHasher {
+    /**
+    Creates a set with a separate subset for every cell of a grid.
+
+    Cells are added row by row, so the result of `labels()` is indexed by `y * width + x`.
+
+    # Example
+
+    ```
+    use advanced_collections::disjoint_set::FastDisjointSet;
+
+    fn main(){
+        //a binary image, connected regions of ones need to be found
+        let image = [
+            [1, 1, 0],
+            [0, 1, 0],
+            [1, 0, 1]
+        ];
+        let mut ds = FastDisjointSet::grid(3, 3);
+        for y in 0..3 {
+            for x in 0..3 {
+                ds.union_neighbors4(x, y, |(ax, ay), (bx, by)| image[ay][ax] == image[by][bx]);
+            }
+        }
+        assert_eq!(ds.labels(), vec![
+            0, 0, 1,
+            2, 0, 1,
+            3, 4, 5
+        ]);
+    }
+    ```
+    */
+    pub fn grid(width: usize, height: usize) -> Self where S: Default {
+        let mut ds = Self::with_capacity_and_hasher(width * height, S::default());
+        for y in 0..height {
+            for x in 0..width {
+                ds.make_set((x, y));
+            }
+        }
+        ds
+    }
+
+    /**
+    Joins the cell with its horizontal and vertical neighbors for which the predicate returns `true`.
+
+    The predicate receives coordinates of the cell and of its neighbor.
+    Only neighbors that belong to the collection are checked,
+    so cells at the edges of the grid are handled correctly.
+    */
+    pub fn union_neighbors4<F>(&mut self, x: usize, y: usize, predicate: F) where F: FnMut((usize, usize), (usize, usize)) -> bool {
+        self.union_neighbors(x, y, &NEIGHBORS4, predicate);
+    }
+
+    /**
+    Joins the cell with its horizontal, vertical and diagonal neighbors
+    for which the predicate returns `true`.
+
+    The predicate receives coordinates of the cell and of its neighbor.
+    Only neighbors that belong to the collection are checked,
+    so cells at the edges of the grid are handled correctly.
+    */
+    pub fn union_neighbors8<F>(&mut self, x: usize, y: usize, predicate: F) where F: FnMut((usize, usize), (usize, usize)) -> bool {
+        self.union_neighbors(x, y, &NEIGHBORS8, predicate);
+    }
+
+    fn union_neighbors<F>(&mut self, x: usize, y: usize, offsets: &[(isize, isize)], mut predicate: F) where F: FnMut((usize, usize), (usize, usize)) -> bool {
+        let cell = (x, y);
+        if !self.contains(&cell) {
+            return;
+        }
+        for &(dx, dy) in offsets {
+            let neighbor = match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
+                (Some(nx), Some(ny)) => (nx, ny),
+                _ => continue
+            };
+            if self.contains(&neighbor) && predicate(cell, neighbor) {
+                self.union(cell, neighbor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid(){
+        let mut ds: DisjointSet<(usize, usize)> = DisjointSet::grid(4, 2);
+        assert_eq!(ds.len(), 8);
+        assert_eq!(ds.labels(), (0..8).collect::<Vec<u32>>());
+        //diagonal pattern connects only with 8-connectivity
+        let image = [[1, 0, 1, 0], [0, 1, 0, 1]];
+        let mut ds4 = ds.clone();
+        for y in 0..2 {
+            for x in 0..4 {
+                ds4.union_neighbors4(x, y, |(ax, ay), (bx, by)| image[ay][ax] == image[by][bx]);
+                ds.union_neighbors8(x, y, |(ax, ay), (bx, by)| image[ay][ax] == image[by][bx]);
+            }
+        }
+        assert_eq!(ds4.labels(), (0..8).collect::<Vec<u32>>());
+        assert_eq!(ds.labels(), vec![0, 1, 0, 1, 1, 0, 1, 0]);
+        //cells outside of the grid are ignored
+        ds.union_neighbors8(10, 10, |_, _| true);
+        assert_eq!(ds.len(), 8);
+    }
+}
@@ -20,6 +20,13 @@ typical operations much more efficient.
 
 mod disjoint_set;
 mod fast_disjoint_set;
+mod weighted_disjoint_set;
+mod grid;
+#[cfg(feature = "rayon")]
+mod par_iter;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 
 pub use self::disjoint_set::DisjointSet;
-pub use self::fast_disjoint_set::FastDisjointSet;
\ No newline at end of file
+pub use self::fast_disjoint_set::FastDisjointSet;
+pub use self::weighted_disjoint_set::WeightedDisjointSet;
\ No newline at end of file
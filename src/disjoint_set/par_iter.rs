@@ -0,0 +1,73 @@
+use std::hash::{BuildHasher, Hash};
+use std::default::Default;
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelIterator};
+use super::disjoint_set::DisjointSet;
+
+/**
+Builds a `DisjointSet` from pairs of elements that belong to the same subset.
+
+Every worker thread builds its own `DisjointSet` from a chunk of pairs
+and partial sets are merged afterwards.
+
+# Example
+
+```
+use advanced_collections::disjoint_set::DisjointSet;
+use rayon::prelude::*;
+
+fn main(){
+    let edges: Vec<(u32, u32)> = (0..1000).map(|i| (i, i + 2)).collect();
+    let mut ds: DisjointSet<u32> = edges.into_par_iter().collect();
+
+    //even and odd numbers form two separate subsets
+    assert_eq!(ds.len(), 1002);
+    assert!(ds.in_union(&0, &1000));
+    assert!(ds.in_union(&1, &1001));
+    assert!(!ds.in_union(&0, &1));
+}
+```
+*/
+impl<T, S> FromParallelIterator<(T, T)> for DisjointSet<T, S>
+    where
+        T: Hash + Eq + Send,
+        S: BuildHasher + Default + Send,
+{
+    fn from_par_iter<I>(par_iter: I) -> Self where I: IntoParallelIterator<Item=(T, T)> {
+        par_iter
+            .into_par_iter()
+            .fold(Self::default, |mut ds, (a, b)| {
+                ds.union(a, b);
+                ds
+            })
+            .reduce(Self::default, |mut a, b| {
+                //merge the smaller set into the bigger one
+                if a.len() < b.len() {
+                    let mut b = b;
+                    b.merge(a);
+                    return b;
+                }
+                a.merge(b);
+                a
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_collect(){
+        let edges: Vec<(u32, u32)> = (0..10_000).map(|i| (i, (i * 7 + 3) % 10_000)).collect();
+        let mut par: DisjointSet<u32> = edges.clone().into_par_iter().collect();
+        let mut seq: DisjointSet<u32> = DisjointSet::new();
+        for (a, b) in edges {
+            seq.union(a, b);
+        }
+        assert_eq!(par.len(), seq.len());
+        for i in 0..10_000 {
+            assert_eq!(par.in_union(&0, &i), seq.in_union(&0, &i), "element {}", i);
+            assert_eq!(par.in_union(&1, &i), seq.in_union(&1, &i), "element {}", i);
+        }
+    }
+}
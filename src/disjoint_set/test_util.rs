@@ -0,0 +1,289 @@
+/*!
+Model-based testing of `DisjointSet`.
+
+Operations are applied both to a `DisjointSet` and to a naive partition stored as
+a `Vec` of `HashSet`s. After every operation both collections need to return the same
+answers and, when requested, contain the same subsets.
+
+The module is available for tests of this crate and, with the `test-util` feature,
+for users who want to add their own regression cases.
+
+# Example
+
+Requires the `test-util` feature, run with `cargo test --features test-util`:
+
+```
+use advanced_collections::disjoint_set::test_util::{check_ops, check_random, Op};
+
+fn main(){
+    //a regression case
+    check_ops(&[Op::Union(1, 2), Op::MakeSet(3), Op::UnionExisting(2, 3), Op::InUnion(1, 3), Op::Check]);
+
+    //randomized operation sequences
+    check_random(42, 100, 200);
+}
+```
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use super::disjoint_set::DisjointSet;
+use crate::test_util::XorShift;
+
+///A single operation applied to a disjoint set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op<T> {
+    ///Adds the element as a new subset, unless it is already stored.
+    MakeSet(T),
+    ///Joins subsets of both elements, adding elements that are not stored yet.
+    Union(T, T),
+    ///Joins subsets of both elements if both are stored, answers whether they were.
+    UnionExisting(T, T),
+    ///Joins subsets of all elements of the group, adding elements that are not stored yet.
+    UnionGroup(Vec<T>),
+    ///Answers whether both elements are stored in the same subset.
+    InUnion(T, T),
+    ///Answers whether the element is stored.
+    Contains(T),
+    ///Removes all elements.
+    Clear,
+    ///Compares all subsets of both collections.
+    Check
+}
+
+/**
+A reference implementation of a disjoint set based on a list of subsets.
+
+All operations scan the list, so it is only suitable for small tests.
+*/
+#[derive(Debug, Clone)]
+pub struct Partition<T> {
+    sets: Vec<HashSet<T>>
+}
+
+impl<T> Default for Partition<T> {
+    fn default() -> Self {
+        Self {
+            sets: Vec::new()
+        }
+    }
+}
+
+impl<T> Partition<T> where T: Hash + Eq + Clone {
+    ///Creates an empty partition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Applies an operation, returning the answer of the query if the operation is one.
+    pub fn apply(&mut self, op: Op<T>) -> Option<bool> {
+        match op {
+            Op::MakeSet(val) => {
+                self.make_set(val);
+                None
+            },
+            Op::Union(a, b) => {
+                let a = self.make_set(a);
+                let b = self.make_set(b);
+                self.join(a, b);
+                None
+            },
+            Op::UnionExisting(a, b) => Some(match (self.find(&a), self.find(&b)) {
+                (Some(a), Some(b)) => {
+                    self.join(a, b);
+                    true
+                },
+                _ => false
+            }),
+            Op::UnionGroup(group) => {
+                let mut group = group.into_iter();
+                if let Some(first) = group.next() {
+                    self.make_set(first.clone());
+                    for val in group {
+                        let a = self.find(&first).unwrap();
+                        let b = self.make_set(val);
+                        self.join(a, b);
+                    }
+                }
+                None
+            },
+            Op::InUnion(a, b) => Some(match (self.find(&a), self.find(&b)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false
+            }),
+            Op::Contains(val) => Some(self.find(&val).is_some()),
+            Op::Clear => {
+                self.sets.clear();
+                None
+            },
+            Op::Check => None
+        }
+    }
+
+    ///Returns the number of elements in all subsets.
+    pub fn len(&self) -> usize {
+        self.sets.iter().map(HashSet::len).sum()
+    }
+
+    ///Checks if the partition is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    ///Returns all subsets of the partition in an arbitrary order.
+    pub fn sets(&self) -> &[HashSet<T>] {
+        &self.sets
+    }
+
+    fn find(&self, val: &T) -> Option<usize> {
+        self.sets.iter().position(|set| set.contains(val))
+    }
+
+    fn make_set(&mut self, val: T) -> usize {
+        match self.find(&val) {
+            Some(idx) => idx,
+            None => {
+                let mut set = HashSet::new();
+                set.insert(val);
+                self.sets.push(set);
+                self.sets.len() - 1
+            }
+        }
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        let (keep, remove) = if a < b { (a, b) } else { (b, a) };
+        let removed = self.sets.swap_remove(remove);
+        self.sets[keep].extend(removed);
+    }
+}
+
+///Applies an operation to the disjoint set, returning the answer of the query if the operation is one.
+pub fn apply<T>(ds: &mut DisjointSet<T>, op: Op<T>) -> Option<bool> where T: Hash + Eq + Clone {
+    match op {
+        Op::MakeSet(val) => {
+            ds.make_set(val);
+            None
+        },
+        Op::Union(a, b) => {
+            ds.union(a, b);
+            None
+        },
+        Op::UnionExisting(a, b) => Some(ds.union_existing(&a, &b)),
+        Op::UnionGroup(group) => {
+            ds.union_group(group);
+            None
+        },
+        Op::InUnion(a, b) => Some(ds.in_union(&a, &b)),
+        Op::Contains(val) => Some(ds.contains(&val)),
+        Op::Clear => {
+            ds.clear();
+            None
+        },
+        Op::Check => None
+    }
+}
+
+//subsets of the disjoint set, grouped by their roots
+fn subsets<T>(ds: &mut DisjointSet<T>) -> Vec<HashSet<T>> where T: Hash + Eq + Clone {
+    let mut by_root: HashMap<T, HashSet<T>> = HashMap::new();
+    for (val, root) in ds.iter_with_roots() {
+        by_root.entry(root.clone()).or_default().insert(val.clone());
+    }
+    by_root.into_values().collect()
+}
+
+/**
+Applies operations to a new disjoint set and to the model, panicking on the first difference.
+
+Panic messages contain the index of the failing operation.
+*/
+pub fn check_ops<T>(ops: &[Op<T>]) where T: Hash + Eq + Clone + Debug {
+    let mut ds = DisjointSet::new();
+    let mut model = Partition::new();
+    for (idx, op) in ops.iter().enumerate() {
+        let actual = apply(&mut ds, op.clone());
+        let expected = model.apply(op.clone());
+        assert_eq!(actual, expected, "answer differs after operation {}: {:?}", idx, op);
+        assert_eq!(ds.len(), model.len(), "length differs after operation {}: {:?}", idx, op);
+        if *op == Op::Check {
+            let actual = subsets(&mut ds);
+            assert_eq!(actual.len(), model.sets.len(), "number of subsets differs after operation {}: {:?}", idx, op);
+            for set in actual.iter() {
+                assert!(model.sets.contains(set), "subset {:?} differs after operation {}: {:?}", set, idx, op);
+            }
+        }
+    }
+}
+
+///Generates a pseudo-random sequence of operations on elements from range `0..max_val`.
+pub fn random_ops(rng: &mut XorShift, len: usize, max_val: u32) -> Vec<Op<u32>> {
+    let max_val = max_val.max(1) as u64;
+    let val = |rng: &mut XorShift| rng.below(max_val) as u32;
+    (0..len).map(|_| match rng.below(16) {
+        0..=2 => Op::MakeSet(val(rng)),
+        3..=5 => Op::Union(val(rng), val(rng)),
+        6..=7 => Op::UnionExisting(val(rng), val(rng)),
+        8 => {
+            let size = rng.below(4);
+            Op::UnionGroup((0..size).map(|_| val(rng)).collect())
+        },
+        9..=11 => Op::InUnion(val(rng), val(rng)),
+        12 => Op::Contains(val(rng)),
+        13 => Op::Clear,
+        _ => Op::Check
+    }).collect()
+}
+
+/**
+Runs `cases` randomized operation sequences of length `len` derived from the seed.
+
+Panics with the seed of the failing case, which can be used to reproduce it.
+*/
+pub fn check_random(seed: u64, cases: usize, len: usize) {
+    let mut rng = XorShift::new(seed);
+    for _ in 0..cases {
+        let case_seed = rng.next_u64();
+        let mut case_rng = XorShift::new(case_seed);
+        let max_val = case_rng.below(32) as u32 + 1;
+        let ops = random_ops(&mut case_rng, len, max_val);
+        let res = ::std::panic::catch_unwind(|| check_ops(&ops));
+        if res.is_err() {
+            panic!("Model check failed for case seed {}", case_seed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random(){
+        check_random(1, 300, 100);
+    }
+
+    #[test]
+    fn test_regressions(){
+        check_ops(&[Op::UnionExisting(1, 2), Op::Union(1, 2), Op::UnionGroup(vec![3, 4, 1]), Op::InUnion(2, 4), Op::Check]);
+        check_ops(&[Op::Union(1, 1), Op::UnionGroup(vec![]), Op::Contains(1), Op::Clear, Op::Contains(1), Op::Check]);
+        check_ops(&["a".to_string(), "b".to_string()].iter().map(|s| Op::MakeSet(s.clone())).chain(Some(Op::Check)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_partition(){
+        let mut p = Partition::new();
+        assert!(p.is_empty());
+        p.apply(Op::Union(1, 2));
+        p.apply(Op::Union(3, 4));
+        p.apply(Op::Union(2, 3));
+        assert_eq!(p.sets().len(), 1);
+        assert_eq!(p.len(), 4);
+        assert_eq!(p.apply(Op::InUnion(1, 4)), Some(true));
+        assert_eq!(p.apply(Op::InUnion(1, 5)), Some(false));
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Add, Sub};
+use std::default::Default;
+
+#[derive(Debug, Clone, Copy)]
+struct Data<W> {
+    pub parent: usize,
+    pub rank: u32,
+    //potential of the element minus potential of its parent
+    pub weight: W
+}
+
+impl<W> Data<W> where W: Default {
+    pub fn new(id: usize) -> Self {
+        Self {
+            parent: id,
+            rank: 0,
+            weight: W::default()
+        }
+    }
+}
+
+/**
+Disjoint set that additionally keeps relations between elements of the same subset.
+
+Every element has a potential and every union records the difference between potentials
+of two elements. It is then possible to query the accumulated difference between any
+two elements of the same subset, which makes the structure suitable for solving
+systems of constraints like "a is 3 more than b".
+
+Path compression composes weights of the compressed edges,
+so all operations keep the complexity of the regular `DisjointSet`.
+
+# Example
+
+```
+use advanced_collections::disjoint_set::WeightedDisjointSet;
+
+fn main(){
+    let mut ds: WeightedDisjointSet<char, i32> = WeightedDisjointSet::new();
+
+    //b is 3 more than a, c is 2 more than b
+    ds.union('a', 'b', 3).unwrap();
+    ds.union('b', 'c', 2).unwrap();
+    assert_eq!(ds.offset(&'a', &'c'), Some(5));
+    assert_eq!(ds.offset(&'c', &'a'), Some(-5));
+
+    //consistent relations are accepted, contradicting ones are rejected
+    assert_eq!(ds.union('a', 'c', 5), Ok(()));
+    assert_eq!(ds.union('a', 'c', 4), Err(5));
+
+    //elements from different subsets are not related
+    ds.make_set('d');
+    assert_eq!(ds.offset(&'a', &'d'), None);
+}
+```
+*/
+#[derive(Clone, Debug)]
+pub struct WeightedDisjointSet<T, W, S=RandomState> where T: Eq+Hash, S: BuildHasher {
+    ids: HashMap<T, usize, S>,
+    data_by_id: Vec<Data<W>>
+}
+
+impl<T, W, S> WeightedDisjointSet<T, W, S>
+    where
+        T: Eq + Hash,
+        W: Copy + Default + PartialEq + Add<Output=W> + Sub<Output=W>,
+        S: BuildHasher
+{
+    /// Creates a new, empty `WeightedDisjointSet`.
+    pub fn new() -> Self where S: Default{
+        Default::default()
+    }
+
+    /**
+    Creates an empty WeightedDisjointSet with the specified capacity.
+
+    The WeightedDisjointSet will be able to hold at least capacity elements without reallocating.
+    If capacity is 0, the WeightedDisjointSet will not allocate.
+    */
+    pub fn with_capacity(capacity: usize) -> Self where S: Default{
+        Self {
+            ids: HashMap::with_capacity_and_hasher(capacity, Default::default()),
+            data_by_id: Vec::with_capacity(capacity)
+        }
+    }
+
+    /**
+    Creates an empty WeightedDisjointSet which will use the given hash builder to hash keys.
+
+    The created set has the default initial capacity.
+    */
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            ids: HashMap::with_hasher(hash_builder),
+            data_by_id: Vec::new()
+        }
+    }
+
+    /**
+    Crates a subset with the provided element.
+
+    If the given element already exists, nothing happens.
+
+    **Complexity:**: O(1)
+    */
+    pub fn make_set(&mut self, val: T) {
+        self.make_or_get_set(val);
+    }
+
+    /**
+    Joins two subsets, recording that the potential of `b` is `weight` more than the potential of `a`.
+
+    If the provided elements do not exist in the collection when this function is called,
+    a new subset with one element gets created prior to joining.
+    If both elements already belong to the same subset, the relation is only verified.
+    When it contradicts the existing one, the existing offset between `a` and `b` is returned as an error.
+
+    **Complexity:** O(α(n)) ≈ O(1)
+    */
+    pub fn union(&mut self, a: T, b: T, weight: W) -> Result<(), W> {
+        let a = self.make_or_get_set(a);
+        let b = self.make_or_get_set(b);
+        let a_root = Self::find_with_path_compression(&mut self.data_by_id, a);
+        let b_root = Self::find_with_path_compression(&mut self.data_by_id, b);
+        let a_weight = self.data_by_id[a].weight;
+        let b_weight = self.data_by_id[b].weight;
+        if a_root == b_root {
+            let existing = b_weight - a_weight;
+            return if existing == weight {
+                Ok(())
+            } else {
+                Err(existing)
+            };
+        }
+
+        if self.data_by_id[a_root].rank < self.data_by_id[b_root].rank {
+            self.data_by_id[a_root].parent = b_root;
+            self.data_by_id[a_root].weight = b_weight - a_weight - weight;
+        } else {
+            self.data_by_id[b_root].parent = a_root;
+            self.data_by_id[b_root].weight = weight + a_weight - b_weight;
+            if self.data_by_id[a_root].rank == self.data_by_id[b_root].rank {
+                self.data_by_id[a_root].rank += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /**
+    Returns the difference between potentials of `b` and `a`.
+
+    Returns `None` if the elements do not belong to the same subset.
+
+    **Complexity:** O(α(n)) ≈ O(1)
+    */
+    pub fn offset(&mut self, a: &T, b: &T) -> Option<W> {
+        let a = *self.ids.get(a)?;
+        let b = *self.ids.get(b)?;
+        if Self::find_with_path_compression(&mut self.data_by_id, a) != Self::find_with_path_compression(&mut self.data_by_id, b) {
+            return None;
+        }
+        Some(self.data_by_id[b].weight - self.data_by_id[a].weight)
+    }
+
+    /**
+    Check if the given element has been added to this collection.
+
+    **Complexity:** O(1)
+    */
+    pub fn contains(&self, val: &T) -> bool {
+        self.ids.contains_key(val)
+    }
+
+    /**
+    Checks if the given two elements are in the same subset.
+
+    **Complexity:** O(α(n)) ≈ O(1)
+    */
+    pub fn in_union(&mut self, a: &T, b: &T) -> bool {
+        self.offset(a, b).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.ids.clear();
+        self.data_by_id.clear()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.data_by_id.reserve(additional);
+        self.ids.reserve(additional);
+    }
+
+    fn make_or_get_set(&mut self, val: T) -> usize {
+        let next_id = self.ids.len();
+        //insert but do not override existing one
+        match self.ids.entry(val) {
+            Entry::Vacant(entry) => {
+                entry.insert(next_id);
+                //make element its own parent
+                self.data_by_id.push(Data::new(next_id));
+                next_id
+            },
+            Entry::Occupied(entry) => *entry.get()
+        }
+    }
+
+    //after the call the element points directly to its root and its weight is relative to the root
+    fn find_with_path_compression(data_by_id: &mut [Data<W>], id: usize) -> usize {
+        let parent = data_by_id[id].parent;
+        if parent == id {
+            return id;
+        }
+        let root = Self::find_with_path_compression(data_by_id, parent);
+        data_by_id[id].weight = data_by_id[id].weight + data_by_id[parent].weight;
+        data_by_id[id].parent = root;
+        root
+    }
+}
+
+impl<T, W, S> Default for WeightedDisjointSet<T, W, S> where T: Eq+Hash, S: BuildHasher + Default {
+    fn default() -> Self {
+        Self{
+            ids: HashMap::default(),
+            data_by_id: Vec::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_and_offset() {
+        let mut ds: WeightedDisjointSet<i32, i64> = WeightedDisjointSet::new();
+        assert_eq!(ds.union(1, 2, 10), Ok(()));
+        assert_eq!(ds.union(3, 4, -2), Ok(()));
+        assert_eq!(ds.offset(&1, &2), Some(10));
+        assert_eq!(ds.offset(&2, &1), Some(-10));
+        assert_eq!(ds.offset(&1, &3), None);
+        assert_eq!(ds.offset(&1, &5), None);
+
+        //join both subsets: 3 is 1 more than 2
+        assert_eq!(ds.union(2, 3, 1), Ok(()));
+        assert_eq!(ds.offset(&1, &3), Some(11));
+        assert_eq!(ds.offset(&1, &4), Some(9));
+        assert_eq!(ds.offset(&4, &2), Some(1));
+        assert!(ds.in_union(&1, &4));
+        assert_eq!(ds.len(), 4);
+    }
+
+    #[test]
+    fn contradiction() {
+        let mut ds: WeightedDisjointSet<&str, i32> = WeightedDisjointSet::new();
+        ds.union("a", "b", 3).unwrap();
+        ds.union("b", "c", 4).unwrap();
+        assert_eq!(ds.union("c", "a", -7), Ok(()));
+        assert_eq!(ds.union("c", "a", 7), Err(-7));
+        assert_eq!(ds.offset(&"a", &"c"), Some(7));
+    }
+
+    #[test]
+    fn long_chain() {
+        let mut ds: WeightedDisjointSet<u32, i64> = WeightedDisjointSet::new();
+        for i in 0..1000 {
+            ds.union(i, i + 1, 1).unwrap();
+        }
+        assert_eq!(ds.offset(&0, &1000), Some(1000));
+        assert_eq!(ds.offset(&500, &250), Some(-250));
+        //after path compression offsets stay valid
+        assert_eq!(ds.offset(&0, &1000), Some(1000));
+    }
+}
@@ -31,7 +31,7 @@ impl <T> Bound<T> where T: Ord {
     }
 
     ///Creates a new instance
-    pub fn new(val: T, is_closed: bool) -> Self {
+    pub const fn new(val: T, is_closed: bool) -> Self {
         Self {
             val,
             is_closed
@@ -190,7 +190,7 @@ impl <T> LowerBound<T> where T: Ord {
     }
     ```
     */
-    pub fn new(val: T, is_closed: bool) -> Self {
+    pub const fn new(val: T, is_closed: bool) -> Self {
         Self {
             bound: Bound::new(val, is_closed)
         }
@@ -550,7 +550,7 @@ impl <T> UpperBound<T> where T: Ord {
     }
     ```
     */
-    pub fn new(val: T, is_closed: bool) -> Self{
+    pub const fn new(val: T, is_closed: bool) -> Self{
         Self {
             bound: Bound::new(val, is_closed)
         }
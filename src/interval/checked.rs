@@ -0,0 +1,126 @@
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use super::interval::Interval;
+
+/*
+Overflow-checked versions of the scalar math operators.
+Both bounds are computed with checked operations of the num-traits crate
+and the whole operation fails if any of them overflows, so results are never
+silently wrapped or truncated.
+*/
+impl<T> Interval<T> where T: Ord {
+    /**
+    Adds the value to both bounds, returning `None` if any of them overflows.
+
+    Adding to an empty interval returns an empty interval.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let i = Interval::closed(100u8, 200);
+        assert_eq!(i.checked_add(&50), Some(Interval::closed(150, 250)));
+        assert_eq!(i.checked_add(&60), None);
+        assert_eq!(Interval::<u8>::empty().checked_add(&255), Some(Interval::empty()));
+    }
+    ```
+    */
+    pub fn checked_add(&self, rhs: &T) -> Option<Self> where T: CheckedAdd {
+        self.checked_map(|val| val.checked_add(rhs))
+    }
+
+    /**
+    Subtracts the value from both bounds, returning `None` if any of them overflows.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let i = Interval::lower_closed(10u32, 20);
+        assert_eq!(i.checked_sub(&10), Some(Interval::lower_closed(0, 10)));
+        assert_eq!(i.checked_sub(&11), None);
+    }
+    ```
+    */
+    pub fn checked_sub(&self, rhs: &T) -> Option<Self> where T: CheckedSub {
+        self.checked_map(|val| val.checked_sub(rhs))
+    }
+
+    /**
+    Multiplies both bounds by the value, returning `None` if any of them overflows.
+
+    Multiplying by a negative value reverses the interval, the same way as the `*` operator.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let i = Interval::lower_closed(-3i8, 6);
+        assert_eq!(i.checked_mul(&-2), Some(Interval::upper_closed(-12, 6)));
+        assert_eq!(i.checked_mul(&30), None);
+    }
+    ```
+    */
+    pub fn checked_mul(&self, rhs: &T) -> Option<Self> where T: CheckedMul {
+        self.checked_map(|val| val.checked_mul(rhs))
+    }
+
+    /**
+    Divides both bounds by the value, returning `None` if the value is zero or any bound overflows.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let i = Interval::closed(-128i8, 0);
+        assert_eq!(i.checked_div(&2), Some(Interval::closed(-64, 0)));
+        assert_eq!(i.checked_div(&0), None);
+        assert_eq!(i.checked_div(&-1), None);
+    }
+    ```
+    */
+    pub fn checked_div(&self, rhs: &T) -> Option<Self> where T: CheckedDiv {
+        self.checked_map(|val| val.checked_div(rhs))
+    }
+
+    //applies the operation to both bounds, preserving their closedness
+    fn checked_map<F>(&self, op: F) -> Option<Self> where F: Fn(&T) -> Option<T> {
+        match self.imp {
+            None => Some(Self::empty()),
+            Some(ref a) => {
+                let lo = op(a.lo.val())?;
+                let up = op(a.up.val())?;
+                Some(Self::create_friendly(lo, a.lo.is_closed(), up, a.up.is_closed()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_operators(){
+        let intervals = [Interval::open(-5i32, 7), Interval::closed(0, 0), Interval::lower_closed(-3, 3), Interval::empty()];
+        for i in intervals.iter() {
+            for &x in [-3, -1, 0, 2, 5].iter() {
+                assert_eq!(i.checked_add(&x), Some(*i + x));
+                assert_eq!(i.checked_sub(&x), Some(*i - x));
+                assert_eq!(i.checked_mul(&x), Some(*i * x));
+                if x != 0 {
+                    assert_eq!(i.checked_div(&x), Some(*i / x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow(){
+        let i = Interval::closed(i64::MIN, 0);
+        assert_eq!(i.checked_add(&1), Some(Interval::closed(i64::MIN + 1, 1)));
+        assert_eq!(i.checked_sub(&1), None);
+        assert_eq!(i.checked_mul(&-1), None);
+        assert_eq!(Interval::closed(0u64, u64::MAX).checked_mul(&2), None);
+        assert_eq!(Interval::closed(0u64, u64::MAX).checked_mul(&1), Some(Interval::closed(0, u64::MAX)));
+    }
+}
@@ -1,7 +1,10 @@
 use std::cmp::{Ord};
-use std::fmt::{Formatter, Display, Result as FmtResult};
+use std::fmt::{Formatter, Display, Write, Result as FmtResult};
 use super::bounds::{LowerBound, UpperBound};
 use std::mem::swap;
+use std::ops::Deref;
+use std::borrow::Borrow;
+use std::sync::Arc;
 
 /*
 Non empty interval - For internal usage only
@@ -75,6 +78,24 @@ pub struct Interval<T> where T: Ord {
     pub (super) imp: Option<NonEmptyInterval<T>>
 }
 
+/**
+Kind of an interval, returned by `Interval::classify()`.
+
+Single-value intervals always have both bounds closed.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntervalKind {
+    ///The interval does not contain any value.
+    Empty,
+    ///The interval contains exactly one value.
+    Single,
+    ///The interval contains more than one value.
+    Proper {
+        lower_closed: bool,
+        upper_closed: bool
+    }
+}
+
 impl<T> Interval<T>  where T: Ord  {
 
 //construction and destruction ====================================================================
@@ -151,6 +172,16 @@ impl<T> Interval<T>  where T: Ord  {
         i
     }
 
+    ///Creates a new interval from provided bounds or an empty interval if bounds do not overlap.
+    pub (super) fn from_bounds_or_empty(lo: LowerBound<T>, up: UpperBound<T>) -> Self {
+        if up.is_separated_from(&lo) || (lo.val() == up.val() && !(lo.is_closed() && up.is_closed())) {
+            return Self::empty();
+        }
+        Self {
+            imp: Some(NonEmptyInterval { lo, up })
+        }
+    }
+
     /**
     Creates an interval that contains only a single value.
 
@@ -261,7 +292,7 @@ impl<T> Interval<T>  where T: Ord  {
     }
     ```
     */
-    pub fn empty() -> Self {
+    pub const fn empty() -> Self {
         Self {
             imp: None
         }
@@ -409,6 +440,82 @@ impl<T> Interval<T>  where T: Ord  {
         }
     }
 
+    /**
+    Get the value of the lower bound projected through a smart pointer.
+
+    Useful for intervals with shared bounds like `Interval<Arc<T>>`.
+    Returns ```None``` if the interval is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    use std::sync::Arc;
+    fn main() {
+       let i = Interval::closed(Arc::new("a".to_string()), Arc::new("c".to_string()));
+       assert_eq!(i.lower_deref().map(String::as_str), Some("a"));
+    }
+    ```
+    */
+    pub fn lower_deref(&self) -> Option<&T::Target> where T: Deref {
+        self.lower().map(|l| l.val().deref())
+    }
+
+    /**
+    Get the value of the upper bound projected through a smart pointer.
+
+    Useful for intervals with shared bounds like `Interval<Arc<T>>`.
+    Returns ```None``` if the interval is empty.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    use std::sync::Arc;
+    fn main() {
+       let i = Interval::closed(Arc::new("a".to_string()), Arc::new("c".to_string()));
+       assert_eq!(i.upper_deref().map(String::as_str), Some("c"));
+    }
+    ```
+    */
+    pub fn upper_deref(&self) -> Option<&T::Target> where T: Deref {
+        self.upper().map(|u| u.val().deref())
+    }
+
+    /**
+    Converts the interval into an interval with bounds shared through `Arc`.
+
+    Cloning, merging and intersecting shared intervals only copies pointers,
+    so it is cheap even for heavyweight bound values.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    use std::sync::Arc;
+    fn main() {
+       let i = Interval::closed("a".to_string(), "c".to_string()).into_shared();
+       let j = i.clone();
+       assert!(Arc::ptr_eq(j.lower().unwrap().val(), i.lower().unwrap().val()));
+    }
+    ```
+    */
+    pub fn into_shared(self) -> Interval<Arc<T>> {
+        match self.imp {
+            None => Interval::empty(),
+            Some(a) => {
+                let (lo, loc) = a.lo.into_tuple();
+                let (up, upc) = a.up.into_tuple();
+                Interval {
+                    imp: Some(NonEmptyInterval {
+                        lo: LowerBound::new(Arc::new(lo), loc),
+                        up: UpperBound::new(Arc::new(up), upc)
+                    })
+                }
+            }
+        }
+    }
+
     /**
     Checks if the interval is empty.
 
@@ -448,6 +555,55 @@ impl<T> Interval<T>  where T: Ord  {
         }
     }
 
+    /**
+    Checks if the interval is degenerate - empty or containing just a single value.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        assert!(Interval::<i32>::empty().is_degenerate());
+        assert!(Interval::single(5).is_degenerate());
+        assert!(!Interval::open(5, 6).is_degenerate());
+    }
+    ```
+    */
+    pub fn is_degenerate(&self) -> bool {
+        self.is_empty() || self.is_single()
+    }
+
+    /**
+    Returns the kind of the interval together with closedness of its bounds.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::{Interval, IntervalKind};
+    fn main() {
+        let describe = |i: Interval<i32>| match i.classify() {
+            IntervalKind::Empty => "empty",
+            IntervalKind::Single => "single",
+            IntervalKind::Proper{lower_closed: true, upper_closed: true} => "closed",
+            IntervalKind::Proper{..} => "partially open"
+        };
+        assert_eq!(describe(Interval::closed(1, 3)), "closed");
+        assert_eq!(describe(Interval::lower_closed(1, 3)), "partially open");
+        assert_eq!(describe(Interval::single(1)), "single");
+    }
+    ```
+    */
+    pub fn classify(&self) -> IntervalKind {
+        match self.imp {
+            None => IntervalKind::Empty,
+            Some(ref a) if a.lo.val() == a.up.val() => IntervalKind::Single,
+            Some(ref a) => IntervalKind::Proper {
+                lower_closed: a.lo.is_closed(),
+                upper_closed: a.up.is_closed()
+            }
+        }
+    }
+
     /**
     Checks if the lower bound of an interval is closed.
 
@@ -512,6 +668,34 @@ impl<T> Interval<T>  where T: Ord  {
         !(self > val || self < val)
     }
 
+    /**
+    Checks if the interval contains a value given in its borrowed form.
+
+    It allows checking `Interval<Arc<T>>` or `Interval<String>`
+    without creating an `Arc` or a `String` for the checked value.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    use std::sync::Arc;
+    fn main() {
+       let i = Interval::closed(Arc::new(3), Arc::new(7));
+       assert!(i.contains_borrowed(&5));
+       assert!(!i.contains_borrowed(&8));
+    }
+    ```
+    */
+    pub fn contains_borrowed<Q>(&self, val: &Q) -> bool where T: Borrow<Q>, Q: Ord + ?Sized {
+        match self.bounds() {
+            None => false,
+            Some((lo, up)) => {
+                let (l, u) = (lo.val().borrow(), up.val().borrow());
+                (l < val || (l == val && lo.is_closed())) && (val < u || (val == u && up.is_closed()))
+            }
+        }
+    }
+
     /**
     Checks if an interval contains another interval.
 
@@ -787,11 +971,145 @@ impl<T> Interval<T>  where T: Ord  {
             swap(&mut s.up, &mut o.up);
         }
     }
+
+    /**
+    Extends the interval minimally so that it contains the given value.
+
+    An empty interval becomes a single-value interval, which is the only case
+    that requires cloning the value.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+       let mut bounding = Interval::empty();
+       for x in vec![5, 2, 9, 4] {
+           bounding.include(x);
+       }
+       assert_eq!(bounding, Interval::closed(2, 9));
+
+       let mut a = Interval::open(1, 3);
+       a.include(3);
+       assert_eq!(a, Interval::upper_closed(1, 3));
+    }
+    ```
+    */
+    pub fn include(&mut self, val: T) where T: Clone {
+        match self.imp.as_mut() {
+            None => *self = Self::single(val),
+            Some(imp) => {
+                if imp.lo > val || (imp.lo == val && !imp.lo.is_closed()) {
+                    imp.lo = LowerBound::new(val, true);
+                } else if imp.up < val || (imp.up == val && !imp.up.is_closed()) {
+                    imp.up = UpperBound::new(val, true);
+                }
+            }
+        }
+    }
+
+    /**
+    Returns parts of the universe that are not covered by this interval.
+
+    The left part contains values below this interval and the right part values above it.
+    Bounds of this interval get their closedness flipped, so a value belongs to
+    exactly one of this interval and the two remainders.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+       let universe = Interval::closed(0, 10);
+       let (left, right) = Interval::lower_closed(3, 5).complement_in(&universe);
+       assert_eq!(left, Some(Interval::lower_closed(0, 3)));
+       assert_eq!(right, Some(Interval::closed(5, 10)));
+       assert_eq!(Interval::closed(0, 5).complement_in(&universe), (None, Some(Interval::upper_closed(5, 10))));
+    }
+    ```
+    */
+    pub fn complement_in(&self, universe: &Self) -> (Option<Self>, Option<Self>) where T: Clone {
+        let (ulo, uup) = match universe.bounds() {
+            None => return (None, None),
+            Some(b) => b
+        };
+        let (lo, up) = match self.bounds() {
+            None => return (Some(universe.clone()), None),
+            Some(b) => b
+        };
+        let below = UpperBound::new(lo.val().clone(), !lo.is_closed());
+        let above = LowerBound::new(up.val().clone(), !up.is_closed());
+        let left = Self::from_bounds_or_empty(ulo.clone(), below.min(uup.clone()));
+        let right = Self::from_bounds_or_empty(above.max(ulo.clone()), uup.clone());
+        let non_empty = |i: Self| if i.is_empty() { None } else { Some(i) };
+        (non_empty(left), non_empty(right))
+    }
+
+//formatting ======================================================================================
+    /**
+    Formats an interval in the form of [2,3) using a custom function for formatting bounds.
+
+    This is useful for bound types that do not implement `Display`
+    or require a non-standard presentation (dates, durations).
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+       let a = Interval::lower_closed(90, 150);
+       let d = a.format_with(|secs| format!("{}m{}s", secs / 60, secs % 60));
+       assert_eq!(d, "[1m30s,2m30s)");
+    }
+    ```
+    */
+    pub fn format_with<F>(&self, fmt_val: F) -> String where F: Fn(&T) -> String {
+        self.display_compact(fmt_val).to_string()
+    }
+
+    /**
+    Creates an adapter that displays an interval using a custom function for formatting bounds.
+
+    Unlike `format_with()` it does not allocate the whole string and can be used directly
+    in formatting macros.
+
+    # Example
+
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+       let a = Interval::closed(1, 2);
+       let d = format!("x in {}", a.display_compact(|v| format!("{}.0", v)));
+       assert_eq!(d, "x in [1.0,2.0]");
+    }
+    ```
+    */
+    pub fn display_compact<F>(&self, fmt_val: F) -> DisplayCompact<'_, T, F> where F: Fn(&T) -> String {
+        DisplayCompact {
+            interval: self,
+            fmt_val
+        }
+    }
+
+    fn fmt_bounds<F>(&self, f: &mut Formatter, fmt_val: F) -> FmtResult where F: Fn(&T, &mut Formatter) -> FmtResult {
+        match &self.imp{
+            None => f.write_char('Ø'),
+            Some(a)=> {
+                f.write_char(if a.lo.is_closed() {'['} else {'('})?;
+                fmt_val(a.lo.val(), f)?;
+                f.write_char(',')?;
+                fmt_val(a.up.val(), f)?;
+                f.write_char(if a.up.is_closed() {']'} else {')'})
+            }
+        }
+    }
 }
 
 /**
 Displays an interval in the form of [2,3).
 
+Formatting options such as precision are applied to both bounds.
+
 # Example
 
 ```
@@ -800,19 +1118,30 @@ fn main() {
    let mut a = Interval::lower_closed(2,3);
    let d = format!("{}", &a);
    assert_eq!(d, "[2,3)");
+   let d = format!("{:03}", &a);
+   assert_eq!(d, "[002,003)");
 }
 ```
 */
 impl<T> Display for Interval<T> where T: Ord + Display {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        match &self.imp{
-            None => write!(f, "Ø"),
-            Some(a)=> {
-                let l = if a.lo.is_closed() {'['} else {'('};
-                let r = if a.up.is_closed() {']'} else {')'};
-                write!(f, "{}{},{}{}", l, a.lo.val(), a.up.val(), r)
-            }
-        }
+        self.fmt_bounds(f, |val, f| val.fmt(f))
+    }
+}
+
+/**
+Displays an interval in the form of [2,3) using a custom function for formatting bounds.
+
+Created by the `Interval::display_compact()` method.
+*/
+pub struct DisplayCompact<'a, T, F> where T: Ord + 'a, F: Fn(&T) -> String {
+    interval: &'a Interval<T>,
+    fmt_val: F
+}
+
+impl<'a, T, F> Display for DisplayCompact<'a, T, F> where T: Ord, F: Fn(&T) -> String {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.interval.fmt_bounds(f, |val, f| f.write_str(&(self.fmt_val)(val)))
     }
 }
 
@@ -1008,5 +1337,86 @@ mod tests {
         assert_eq!(Interval::lower_closed(4,7).into_intersection(Interval::empty()), Interval::empty());
     }
 
-}
+    #[test]
+    fn test_display(){
+        assert_eq!(format!("{}", Interval::open(1,2)), "(1,2)");
+        assert_eq!(format!("{}", Interval::single(-3)), "[-3,-3]");
+        assert_eq!(format!("{}", Interval::<i32>::empty()), "Ø");
+        assert_eq!(format!("{:+}", Interval::upper_closed(1,2)), "(+1,+2]");
+    }
+
+    #[test]
+    fn test_format_with(){
+        let i = Interval::lower_closed(1, 3);
+        assert_eq!(i.format_with(|v| format!("<{}>", v)), "[<1>,<3>)");
+        assert_eq!(format!("{}", i.display_compact(|v| (v * 10).to_string())), "[10,30)");
+        let e: Interval<i32> = Interval::empty();
+        assert_eq!(e.format_with(|v| v.to_string()), "Ø");
+    }
 
+    #[test]
+    fn test_include(){
+        let mut a = Interval::open(2, 5);
+        a.include(3);
+        assert_eq!(a, Interval::open(2, 5));
+        a.include(2);
+        assert_eq!(a, Interval::lower_closed(2, 5));
+        a.include(7);
+        assert_eq!(a, Interval::closed(2, 7));
+        a.include(-1);
+        assert_eq!(a, Interval::closed(-1, 7));
+        let mut e = Interval::empty();
+        e.include("b".to_string());
+        e.include("a".to_string());
+        assert_eq!(e, Interval::closed("a".to_string(), "b".to_string()));
+    }
+
+    #[test]
+    fn test_classify(){
+        assert_eq!(Interval::<u8>::empty().classify(), IntervalKind::Empty);
+        assert_eq!(Interval::single(1).classify(), IntervalKind::Single);
+        assert_eq!(Interval::open(1, 2).classify(), IntervalKind::Proper{lower_closed: false, upper_closed: false});
+        assert_eq!(Interval::upper_closed(1, 2).classify(), IntervalKind::Proper{lower_closed: false, upper_closed: true});
+        assert!(Interval::closed(2, 2).is_degenerate());
+        assert!(!Interval::closed(2, 3).is_degenerate());
+    }
+
+    #[test]
+    fn test_complement_in(){
+        let universe = Interval::open(0, 10);
+        assert_eq!(Interval::closed(2, 4).complement_in(&universe), (Some(Interval::open(0, 2)), Some(Interval::open(4, 10))));
+        assert_eq!(Interval::open(2, 4).complement_in(&universe), (Some(Interval::upper_closed(0, 2)), Some(Interval::lower_closed(4, 10))));
+        assert_eq!(Interval::single(5).complement_in(&universe), (Some(Interval::open(0, 5)), Some(Interval::open(5, 10))));
+        //touching and exceeding the universe
+        assert_eq!(Interval::closed(-5, 3).complement_in(&universe), (None, Some(Interval::open(3, 10))));
+        assert_eq!(Interval::closed(0, 10).complement_in(&universe), (None, None));
+        assert_eq!(Interval::open(0, 10).complement_in(&universe), (None, None));
+        assert_eq!(Interval::closed(20, 30).complement_in(&universe), (Some(universe), None));
+        assert_eq!(Interval::closed(-20, -10).complement_in(&universe), (None, Some(universe)));
+        assert_eq!(Interval::closed(1, 2).complement_in(&Interval::closed(0, 10)), (Some(Interval::lower_closed(0, 1)), Some(Interval::upper_closed(2, 10))));
+        //empty intervals
+        assert_eq!(Interval::empty().complement_in(&universe), (Some(universe), None));
+        assert_eq!(Interval::closed(1, 2).complement_in(&Interval::empty()), (None, None));
+    }
+
+    #[test]
+    fn test_shared(){
+        let a = Interval::closed("b".to_string(), "d".to_string()).into_shared();
+        let b = Interval::closed("c".to_string(), "e".to_string()).into_shared();
+        let lo = a.lower().unwrap().val().clone();
+        let up = b.upper().unwrap().val().clone();
+        //bounds are moved or shared, never deeply cloned
+        let merged = a.clone().into_merged(b.clone()).unwrap();
+        assert!(Arc::ptr_eq(merged.lower().unwrap().val(), &lo));
+        assert!(Arc::ptr_eq(merged.upper().unwrap().val(), &up));
+        let common = a.into_intersection(b);
+        assert_eq!(common.lower_deref().map(String::as_str), Some("c"));
+        assert_eq!(common.upper_deref().map(String::as_str), Some("d"));
+        assert!(common.contains_borrowed(&"cc".to_string()));
+        assert!(common.contains_borrowed(&"d".to_string()));
+        assert!(!common.contains_borrowed(&"e".to_string()));
+        assert!(Interval::closed("a".to_string(), "c".to_string()).contains_borrowed("b"));
+        assert!(Interval::<String>::empty().into_shared().is_empty());
+        assert!(!Interval::open(1, 3).contains_borrowed(&1));
+    }
+}
@@ -0,0 +1,89 @@
+use super::bounds::{LowerBound, UpperBound};
+use super::interval::{Interval, NonEmptyInterval};
+
+/*
+Generic constructors cannot be const functions because they compare values using the Ord trait.
+Primitive integers can be compared in const context,
+so each integer type gets its own set of const constructors.
+*/
+macro_rules! impl_const_constructors {
+    ($($t:ty),*) => {$(
+        impl Interval<$t> {
+            ///Const version of `Interval::new()`, panics (or fails compilation) if the data is invalid.
+            pub const fn const_new(lo: $t, loc: bool, up: $t, upc: bool) -> Self {
+                if lo > up {
+                    panic!("Lower bound of an interval needs to be less than the upper one.");
+                }
+
+                if lo == up && (!loc || !upc) {
+                    panic!("Single elements need to have closed bounds.");
+                }
+
+                Self {
+                    imp: Some(NonEmptyInterval {
+                        lo: LowerBound::new(lo, loc),
+                        up: UpperBound::new(up, upc)
+                    })
+                }
+            }
+
+            ///Const version of `Interval::open()`.
+            pub const fn const_open(lo: $t, up: $t) -> Self {
+                Self::const_new(lo, false, up, false)
+            }
+
+            ///Const version of `Interval::closed()`.
+            pub const fn const_closed(lo: $t, up: $t) -> Self {
+                Self::const_new(lo, true, up, true)
+            }
+
+            ///Const version of `Interval::lower_closed()`.
+            pub const fn const_lower_closed(lo: $t, up: $t) -> Self {
+                Self::const_new(lo, true, up, false)
+            }
+
+            ///Const version of `Interval::upper_closed()`.
+            pub const fn const_upper_closed(lo: $t, up: $t) -> Self {
+                Self::const_new(lo, false, up, true)
+            }
+
+            ///Const version of `Interval::single()`.
+            pub const fn const_single(val: $t) -> Self {
+                Self::const_new(val, true, val, true)
+            }
+        }
+    )*};
+}
+
+impl_const_constructors!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static BUCKETS: [Interval<u32>; 4] = [
+        Interval::<u32>::const_lower_closed(0, 10),
+        Interval::<u32>::const_lower_closed(10, 100),
+        Interval::<u32>::const_closed(100, 1000),
+        Interval::<u32>::const_single(5000)
+    ];
+
+    const NONE: Interval<i64> = Interval::empty();
+
+    #[test]
+    fn test_const_constructors(){
+        assert_eq!(BUCKETS[0], Interval::lower_closed(0, 10));
+        assert_eq!(BUCKETS[1], Interval::lower_closed(10, 100));
+        assert_eq!(BUCKETS[2], Interval::closed(100, 1000));
+        assert_eq!(BUCKETS[3], Interval::single(5000));
+        assert_eq!(Interval::<i8>::const_open(-3, 3), Interval::open(-3, 3));
+        assert_eq!(Interval::<usize>::const_upper_closed(1, 2), Interval::upper_closed(1, 2));
+        assert!(NONE.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_const_invalid(){
+        Interval::<u8>::const_open(3, 3);
+    }
+}
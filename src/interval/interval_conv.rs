@@ -0,0 +1,109 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use super::interval::Interval;
+
+/**
+Error returned when a closed interval cannot be created because
+the lower bound is greater than the upper one.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidBoundsError;
+
+impl Display for InvalidBoundsError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("lower bound of an interval is greater than the upper one")
+    }
+}
+
+impl Error for InvalidBoundsError {}
+
+/**
+Creates a closed interval from a pair of values.
+
+# Example
+```
+use advanced_collections::interval::Interval;
+use std::convert::TryFrom;
+fn main() {
+    assert_eq!(Interval::try_from((1, 3)), Ok(Interval::closed(1, 3)));
+    assert!(Interval::try_from((3, 1)).is_err());
+}
+```
+*/
+impl<T> TryFrom<(T, T)> for Interval<T> where T: Ord {
+    type Error = InvalidBoundsError;
+
+    fn try_from(value: (T, T)) -> Result<Self, Self::Error> {
+        let (lo, up) = value;
+        if lo > up {
+            return Err(InvalidBoundsError);
+        }
+        Ok(Self::closed(lo, up))
+    }
+}
+
+/**
+Creates a closed interval from an array of two values.
+
+# Example
+```
+use advanced_collections::interval::Interval;
+use std::convert::TryFrom;
+fn main() {
+    assert_eq!(Interval::try_from([1, 3]), Ok(Interval::closed(1, 3)));
+    assert!(Interval::try_from([3, 1]).is_err());
+}
+```
+*/
+impl<T> TryFrom<[T; 2]> for Interval<T> where T: Ord {
+    type Error = InvalidBoundsError;
+
+    fn try_from(value: [T; 2]) -> Result<Self, Self::Error> {
+        let [lo, up] = value;
+        Self::try_from((lo, up))
+    }
+}
+
+/**
+Converts an interval into a pair of bound values, `None` for empty intervals.
+
+Information about closedness of bounds is lost.
+
+# Example
+```
+use advanced_collections::interval::Interval;
+fn main() {
+    let pair: Option<(i32, i32)> = Interval::open(1, 3).into();
+    assert_eq!(pair, Some((1, 3)));
+    let pair: Option<(i32, i32)> = Interval::empty().into();
+    assert_eq!(pair, None);
+}
+```
+*/
+impl<T> From<Interval<T>> for Option<(T, T)> where T: Ord {
+    fn from(interval: Interval<T>) -> Self {
+        interval.into_tuple().map(|(lo, _, up, _)| (lo, up))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from(){
+        assert_eq!(Interval::try_from((2, 2)), Ok(Interval::single(2)));
+        assert_eq!(Interval::try_from(["a", "b"]), Ok(Interval::closed("a", "b")));
+        assert_eq!(Interval::try_from((5u8, 4)), Err(InvalidBoundsError));
+        assert_eq!(InvalidBoundsError.to_string(), "lower bound of an interval is greater than the upper one");
+    }
+
+    #[test]
+    fn test_into_pair(){
+        let pair: Option<(u8, u8)> = Interval::upper_closed(1, 2).into();
+        assert_eq!(pair, Some((1, 2)));
+        let i: Interval<u8> = Interval::try_from(pair.unwrap()).unwrap();
+        assert_eq!(i, Interval::closed(1, 2));
+    }
+}
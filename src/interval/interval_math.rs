@@ -19,8 +19,8 @@ impl<T, U> Add<U> for Interval<T> where T:Ord+Add<U, Output=T>, U:Clone {
 }
 
 
-impl<T> AddAssign<T> for Interval<T> where T:Ord+AddAssign + Clone {
-    fn add_assign(&mut self, rhs: T) {
+impl<T, U> AddAssign<U> for Interval<T> where T: Ord + AddAssign<U>, U: Clone {
+    fn add_assign(&mut self, rhs: U) {
         if let Some(ref mut a) = self.imp{
             a.lo += rhs.clone();
             a.up += rhs;
@@ -46,8 +46,8 @@ impl<T, U> Sub<U> for Interval<T> where T:Ord+Sub<U, Output=T>, U:Clone {
     }
 }
 
-impl<T> SubAssign<T> for Interval<T> where T:Ord + SubAssign + Clone {
-    fn sub_assign(&mut self, rhs: T) {
+impl<T, U> SubAssign<U> for Interval<T> where T: Ord + SubAssign<U>, U: Clone {
+    fn sub_assign(&mut self, rhs: U) {
         if let Some(ref mut a) = self.imp{
             a.lo -= rhs.clone();
             a.up -= rhs;
@@ -74,8 +74,8 @@ impl<T, U> Mul<U> for Interval<T> where T:Ord+Mul<U, Output=T>, U: Clone {
 }
 
 
-impl<T> MulAssign<T> for Interval<T> where T:Ord+MulAssign+Clone {
-    fn mul_assign(&mut self, rhs: T) {
+impl<T, U> MulAssign<U> for Interval<T> where T: Ord + MulAssign<U>, U: Clone {
+    fn mul_assign(&mut self, rhs: U) {
         if let Some(ref mut a) = self.imp{
             a.lo *= rhs.clone();
             a.up *= rhs;
@@ -104,8 +104,8 @@ impl<T, U> Div<U> for Interval<T> where T:Ord+Div<U, Output=T>, U:Clone {
 }
 
 
-impl<T> DivAssign<T> for Interval<T> where T: Ord+DivAssign + Clone {
-    fn div_assign(&mut self, rhs: T) {
+impl<T, U> DivAssign<U> for Interval<T> where T: Ord + DivAssign<U>, U: Clone {
+    fn div_assign(&mut self, rhs: U) {
         if let Some(ref mut a) = self.imp{
             a.lo /= rhs.clone();
             a.up /= rhs;
@@ -175,4 +175,35 @@ mod tests {
         assert_eq!(i, Interval::empty())
 
     }
+
+    #[test]
+    fn test_shift_time(){
+        use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+        let start = UNIX_EPOCH + Duration::from_secs(100);
+        let end = start + Duration::from_secs(60);
+        let mut window = Interval::lower_closed(start, end);
+        let hour = Duration::from_secs(3600);
+        let shifted: Interval<SystemTime> = window + hour;
+        assert_eq!(shifted, Interval::lower_closed(start + hour, end + hour));
+        assert_eq!(shifted - hour, window);
+        window += hour;
+        assert_eq!(window, shifted);
+        window -= hour;
+        assert_eq!(window, Interval::lower_closed(start, end));
+
+        let now = Instant::now();
+        let mut i = Interval::closed(now, now + hour);
+        i += Duration::from_secs(1);
+        assert_eq!(i, Interval::closed(now + Duration::from_secs(1), now + hour + Duration::from_secs(1)));
+
+        let mut empty: Interval<SystemTime> = Interval::empty();
+        empty += hour;
+        assert_eq!(empty - hour, Interval::empty());
+
+        let mut d = Interval::open(Duration::from_secs(2), Duration::from_secs(4));
+        d *= 2;
+        d /= 4;
+        assert_eq!(d, Interval::open(Duration::from_secs(1), Duration::from_secs(2)));
+    }
 }
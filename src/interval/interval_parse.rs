@@ -0,0 +1,224 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Write, Result as FmtResult};
+use std::str::FromStr;
+use super::bounds::{LowerBound, UpperBound};
+use super::interval::Interval;
+use super::interval_conv::InvalidBoundsError;
+
+/**
+Grammar of the textual notation of intervals and bounds.
+
+The notation is produced by `Display` and accepted by `FromStr` implementations
+of `Interval`, `LowerBound` and `UpperBound`, so `format!("{}", i).parse()` returns
+the original value for any valid interval whose values do not contain commas in their
+textual form. No whitespace is allowed outside of values.
+*/
+pub const INTERVAL_GRAMMAR: &str = r#"interval    = empty | lower "," upper
+empty       = "Ø" | "∅"
+lower       = ( "[" | "(" ) value
+upper       = value ( "]" | ")" )
+value       = any text accepted by the FromStr implementation of the value type, without ","
+"#;
+
+/**
+Error returned when parsing an interval or a bound fails.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseIntervalError<E> {
+    ///The text does not match the interval grammar.
+    InvalidSyntax,
+    ///A value could not be parsed.
+    InvalidValue(E),
+    ///Values are parsed correctly but do not form a valid interval.
+    InvalidBounds(InvalidBoundsError)
+}
+
+impl<E> Display for ParseIntervalError<E> where E: Display {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            ParseIntervalError::InvalidSyntax => f.write_str("invalid interval syntax"),
+            ParseIntervalError::InvalidValue(e) => write!(f, "invalid interval value: {}", e),
+            ParseIntervalError::InvalidBounds(e) => Display::fmt(e, f)
+        }
+    }
+}
+
+impl<E> Error for ParseIntervalError<E> where E: Error {}
+
+/**
+Displays a lower bound in the form of `[3` or `(3`.
+
+# Example
+
+```
+use advanced_collections::interval::LowerBound;
+fn main() {
+    let b = LowerBound::new(3, false);
+    assert_eq!(b.to_string(), "(3");
+    assert_eq!("(3".parse(), Ok(b));
+}
+```
+*/
+impl<T> Display for LowerBound<T> where T: Ord + Display {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_char(if self.is_closed() {'['} else {'('})?;
+        self.val().fmt(f)
+    }
+}
+
+/**
+Displays an upper bound in the form of `3]` or `3)`.
+
+# Example
+
+```
+use advanced_collections::interval::UpperBound;
+fn main() {
+    let b = UpperBound::new(3, true);
+    assert_eq!(b.to_string(), "3]");
+    assert_eq!("3]".parse(), Ok(b));
+}
+```
+*/
+impl<T> Display for UpperBound<T> where T: Ord + Display {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        self.val().fmt(f)?;
+        f.write_char(if self.is_closed() {']'} else {')'})
+    }
+}
+
+impl<T> FromStr for LowerBound<T> where T: Ord + FromStr {
+    type Err = ParseIntervalError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_closed = match s.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let val = parse_value(&s[1..])?;
+        Ok(LowerBound::new(val, is_closed))
+    }
+}
+
+impl<T> FromStr for UpperBound<T> where T: Ord + FromStr {
+    type Err = ParseIntervalError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_closed = match s.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let val = parse_value(&s[..s.len() - 1])?;
+        Ok(UpperBound::new(val, is_closed))
+    }
+}
+
+/**
+Parses an interval from the notation produced by `Display`.
+
+The accepted grammar is described by `INTERVAL_GRAMMAR`.
+
+# Example
+
+```
+use advanced_collections::interval::{Interval, ParseIntervalError};
+fn main() {
+    let i: Interval<i32> = "[2,7)".parse().unwrap();
+    assert_eq!(i, Interval::lower_closed(2, 7));
+    assert_eq!("Ø".parse::<Interval<i32>>(), Ok(Interval::empty()));
+    assert_eq!(i.to_string().parse(), Ok(i));
+    assert_eq!("[2;7)".parse::<Interval<i32>>(), Err(ParseIntervalError::InvalidSyntax));
+}
+```
+*/
+impl<T> FromStr for Interval<T> where T: Ord + FromStr {
+    type Err = ParseIntervalError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "Ø" || s == "∅" {
+            return Ok(Interval::empty());
+        }
+        let comma = s.find(',').ok_or(ParseIntervalError::InvalidSyntax)?;
+        let lo: LowerBound<T> = s[..comma].parse()?;
+        let up: UpperBound<T> = s[comma + 1..].parse()?;
+        from_parsed_bounds(lo, up)
+    }
+}
+
+//creates an interval from parsed bounds, checking that they form a valid interval
+pub(super) fn from_parsed_bounds<T, E>(lo: LowerBound<T>, up: UpperBound<T>) -> Result<Interval<T>, ParseIntervalError<E>> where T: Ord {
+    if lo.val() > up.val() || (lo.val() == up.val() && !(lo.is_closed() && up.is_closed())) {
+        return Err(ParseIntervalError::InvalidBounds(InvalidBoundsError));
+    }
+    Ok(Interval::from_bounds(lo, up))
+}
+
+fn parse_value<T>(s: &str) -> Result<T, ParseIntervalError<T::Err>> where T: FromStr {
+    if s.is_empty() || s.contains(',') {
+        return Err(ParseIntervalError::InvalidSyntax);
+    }
+    s.parse().map_err(ParseIntervalError::InvalidValue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+
+    //creates a valid interval from arbitrary data
+    fn make_interval<T>(a: T, ac: bool, b: T, bc: bool, empty: bool) -> Interval<T> where T: Ord {
+        if empty {
+            return Interval::empty();
+        }
+        let (lo, up) = if a <= b { (a, b) } else { (b, a) };
+        if lo == up {
+            Interval::new(lo, true, up, true)
+        } else {
+            Interval::new(lo, ac, up, bc)
+        }
+    }
+
+    quickcheck! {
+        fn round_trip_i64(a: i64, ac: bool, b: i64, bc: bool, empty: bool) -> bool {
+            let i = make_interval(a, ac, b, bc, empty);
+            i.to_string().parse() == Ok(i)
+        }
+
+        fn round_trip_u8(a: u8, ac: bool, b: u8, bc: bool, empty: bool) -> bool {
+            let i = make_interval(a, ac, b, bc, empty);
+            i.to_string().parse() == Ok(i)
+        }
+
+        fn round_trip_bounds(a: i32, closed: bool) -> bool {
+            let lo = LowerBound::new(a, closed);
+            let up = UpperBound::new(a, closed);
+            lo.to_string().parse() == Ok(lo) && up.to_string().parse() == Ok(up)
+        }
+    }
+
+    #[test]
+    fn test_parse_errors(){
+        let parse = |s: &str| s.parse::<Interval<i32>>();
+        assert_eq!(parse("(1,2]"), Ok(Interval::upper_closed(1, 2)));
+        assert_eq!(parse("[-3,-3]"), Ok(Interval::single(-3)));
+        assert_eq!(parse("∅"), Ok(Interval::empty()));
+        assert_eq!(parse(""), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parse("[1,2"), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parse("1,2]"), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parse("[1,2,3]"), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parse("[,2]"), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parse("[1, 2]"), Err(ParseIntervalError::InvalidValue(" 2".parse::<i32>().unwrap_err())));
+        assert_eq!(parse("[3,2]"), Err(ParseIntervalError::InvalidBounds(InvalidBoundsError)));
+        assert_eq!(parse("[2,2)"), Err(ParseIntervalError::InvalidBounds(InvalidBoundsError)));
+        assert_eq!(parse("[a,2)").unwrap_err().to_string(), "invalid interval value: invalid digit found in string");
+    }
+
+    #[test]
+    fn test_display_bounds(){
+        assert_eq!(format!("{:02}", LowerBound::new(5, true)), "[05");
+        assert_eq!(UpperBound::new("x".to_string(), false).to_string(), "x)");
+        assert!(INTERVAL_GRAMMAR.contains("interval"));
+    }
+}
@@ -0,0 +1,235 @@
+use std::str::FromStr;
+use super::bounds::{LowerBound, UpperBound};
+use super::interval::Interval;
+use super::interval_parse::{from_parsed_bounds, ParseIntervalError};
+
+//tokens accepted as infinite values at the lower and upper end of an interval
+const NEG_INFINITY: [&str; 5] = ["-inf", "-infinity", "-∞", "−∞", "-Infinity"];
+const POS_INFINITY: [&str; 7] = ["inf", "+inf", "infinity", "+infinity", "∞", "+∞", "Infinity"];
+
+/**
+A configurable parser of the interval notation.
+
+By default it accepts exactly the same notation as the `FromStr` implementation of `Interval`,
+described by `INTERVAL_GRAMMAR`. Options make it accept notations used by other tools:
+
+- `whitespace` - allows whitespace around brackets, values and the separator, e.g. `[ 1 , 2 )`.
+- `reversed_brackets` - accepts the ISO 31-11 notation of open bounds, e.g. `]1,2[`.
+- `standard_brackets` - accepts `(` and `)` as open bounds, enabled by default.
+- `decimal_comma` - values use comma as the decimal separator and `;` separates values, e.g. `[1,5;2,5]`.
+  Commas in values are replaced with dots before they are parsed, which suits any value type
+  whose `FromStr` implementation accepts decimal numbers.
+- `infinity` - replaces infinity tokens (`-inf`, `-∞`, `inf`, `+∞` and similar) with the provided values.
+  Infinite bounds are closed at these values, regardless of the bracket.
+
+# Example
+
+```
+use advanced_collections::interval::{Interval, IntervalParser};
+
+fn main() {
+    let parser = IntervalParser::new()
+        .whitespace(true)
+        .reversed_brackets(true)
+        .decimal_comma(true)
+        .infinity(i64::MIN, i64::MAX);
+    let i: Interval<i64> = parser.parse(" ]1 ; 2] ").unwrap();
+    assert_eq!(i, Interval::upper_closed(1, 2));
+    let i: Interval<i64> = parser.parse("(-∞; 0[").unwrap();
+    assert_eq!(i, Interval::lower_closed(i64::MIN, 0));
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct IntervalParser<T> {
+    whitespace: bool,
+    standard_brackets: bool,
+    reversed_brackets: bool,
+    decimal_comma: bool,
+    infinity: Option<(T, T)>
+}
+
+impl<T> IntervalParser<T> {
+    ///Creates a parser accepting the same notation as `FromStr`.
+    pub fn new() -> Self {
+        Self {
+            whitespace: false,
+            standard_brackets: true,
+            reversed_brackets: false,
+            decimal_comma: false,
+            infinity: None
+        }
+    }
+
+    ///Allows whitespace around brackets, values and the separator.
+    pub fn whitespace(mut self, allowed: bool) -> Self {
+        self.whitespace = allowed;
+        self
+    }
+
+    ///Allows `(` and `)` as open bounds.
+    pub fn standard_brackets(mut self, allowed: bool) -> Self {
+        self.standard_brackets = allowed;
+        self
+    }
+
+    ///Allows `]` and `[` as open lower and upper bounds.
+    pub fn reversed_brackets(mut self, allowed: bool) -> Self {
+        self.reversed_brackets = allowed;
+        self
+    }
+
+    ///Treats comma as the decimal separator of values and `;` as the separator of values.
+    pub fn decimal_comma(mut self, enabled: bool) -> Self {
+        self.decimal_comma = enabled;
+        self
+    }
+
+    ///Accepts infinity tokens, replacing them with the given lower and upper values.
+    pub fn infinity(mut self, lower: T, upper: T) -> Self {
+        self.infinity = Some((lower, upper));
+        self
+    }
+}
+
+impl<T> IntervalParser<T> where T: Ord + FromStr + Clone {
+    /**
+    Parses an interval using the configured notation.
+
+    Returns the same errors as the `FromStr` implementation of `Interval`.
+    */
+    pub fn parse(&self, s: &str) -> Result<Interval<T>, ParseIntervalError<T::Err>> {
+        let s = self.trim(s);
+        if s == "Ø" || s == "∅" {
+            return Ok(Interval::empty());
+        }
+        let mut chars = s.chars();
+        let (first, last) = match (chars.next(), chars.next_back()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let lo_closed = match first {
+            '[' => true,
+            '(' if self.standard_brackets => false,
+            ']' if self.reversed_brackets => false,
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let up_closed = match last {
+            ']' => true,
+            ')' if self.standard_brackets => false,
+            '[' if self.reversed_brackets => false,
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let inner = chars.as_str();
+        let separator = if self.decimal_comma {';'} else {','};
+        let mut parts = inner.split(separator);
+        let (lo, up) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(lo), Some(up), None) => (lo, up),
+            _ => return Err(ParseIntervalError::InvalidSyntax)
+        };
+        let lo = match self.parse_value(lo, &NEG_INFINITY)? {
+            Value::Finite(val) => LowerBound::new(val, lo_closed),
+            Value::Infinite => LowerBound::new(self.infinity.as_ref().unwrap().0.clone(), true)
+        };
+        let up = match self.parse_value(up, &POS_INFINITY)? {
+            Value::Finite(val) => UpperBound::new(val, up_closed),
+            Value::Infinite => UpperBound::new(self.infinity.as_ref().unwrap().1.clone(), true)
+        };
+        from_parsed_bounds(lo, up)
+    }
+
+    fn trim<'a>(&self, s: &'a str) -> &'a str {
+        if self.whitespace {
+            s.trim()
+        } else {
+            s
+        }
+    }
+
+    fn parse_value(&self, s: &str, infinity: &[&str]) -> Result<Value<T>, ParseIntervalError<T::Err>> {
+        let s = self.trim(s);
+        if s.is_empty() || (!self.decimal_comma && s.contains(',')) {
+            return Err(ParseIntervalError::InvalidSyntax);
+        }
+        if self.infinity.is_some() && infinity.contains(&s) {
+            return Ok(Value::Infinite);
+        }
+        let res = if self.decimal_comma {
+            s.replace(',', ".").parse()
+        } else {
+            s.parse()
+        };
+        res.map(Value::Finite).map_err(ParseIntervalError::InvalidValue)
+    }
+}
+
+impl<T> Default for IntervalParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+enum Value<T> {
+    Finite(T),
+    Infinite
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::interval_conv::InvalidBoundsError;
+
+    #[test]
+    fn test_default_matches_from_str(){
+        let parser: IntervalParser<i32> = IntervalParser::new();
+        for s in ["[1,2)", "(1,2]", "Ø", "[3,3]", "", "[1, 2]", "]1,2[", "[3,2]", "[1,2,3]", "[a,2)"].iter() {
+            assert_eq!(parser.parse(s), s.parse::<Interval<i32>>(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_options(){
+        let ws: IntervalParser<i32> = IntervalParser::new().whitespace(true);
+        assert_eq!(ws.parse(" [ 1 , 2 ) "), Ok(Interval::lower_closed(1, 2)));
+        assert_eq!(ws.parse(" ∅ "), Ok(Interval::empty()));
+        assert_eq!(ws.parse("[ , 2)"), Err(ParseIntervalError::InvalidSyntax));
+
+        let iso: IntervalParser<i32> = IntervalParser::new().reversed_brackets(true).standard_brackets(false);
+        assert_eq!(iso.parse("]1,2["), Ok(Interval::open(1, 2)));
+        assert_eq!(iso.parse("[1,2["), Ok(Interval::lower_closed(1, 2)));
+        assert_eq!(iso.parse("(1,2]"), Err(ParseIntervalError::InvalidSyntax));
+
+        let inf = IntervalParser::new().infinity(i32::MIN, i32::MAX);
+        assert_eq!(inf.parse("(-inf,3)"), Ok(Interval::lower_closed(i32::MIN, 3)));
+        assert_eq!(inf.parse("[0,+∞)"), Ok(Interval::closed(0, i32::MAX)));
+        assert_eq!(inf.parse("(inf,3)"), Err(ParseIntervalError::InvalidValue("inf".parse::<i32>().unwrap_err())));
+        assert_eq!(inf.parse("(+∞,-∞)"), Err(ParseIntervalError::InvalidValue("+∞".parse::<i32>().unwrap_err())));
+        assert_eq!(inf.parse("[5,-5]"), Err(ParseIntervalError::InvalidBounds(InvalidBoundsError)));
+    }
+
+    //a fixed-point number with two decimal places
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct Fixed(i64);
+
+    impl FromStr for Fixed {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, ()> {
+            let (int, frac) = s.split_once('.').unwrap_or((s, "0"));
+            let int: i64 = int.parse().map_err(|_| ())?;
+            let frac: i64 = format!("{:0<2}", frac).parse().map_err(|_| ())?;
+            Ok(Fixed(int * 100 + frac))
+        }
+    }
+
+    #[test]
+    fn test_decimal_comma(){
+        let parser = IntervalParser::new().decimal_comma(true).whitespace(true);
+        assert_eq!(parser.parse("[1,5; 2,25)"), Ok(Interval::lower_closed(Fixed(150), Fixed(225))));
+
+        let parser: IntervalParser<u64> = IntervalParser::new().decimal_comma(true);
+        assert_eq!(parser.parse("[1;2]"), Ok(Interval::closed(1, 2)));
+        assert_eq!(parser.parse("[1,2]"), Err(ParseIntervalError::InvalidSyntax));
+        assert_eq!(parser.parse("[1;2;3]"), Err(ParseIntervalError::InvalidSyntax));
+    }
+}
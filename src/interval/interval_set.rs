@@ -0,0 +1,577 @@
+use std::iter::{Extend, FromIterator, IntoIterator};
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+use std::slice::Iter as SliceIter;
+use std::vec::IntoIter as VecIntoIter;
+use super::bounds::{LowerBound, UpperBound};
+use super::interval::Interval;
+use super::interval_slice::IntervalSlice;
+
+/**
+A set of values represented as a collection of disjoint intervals.
+
+Intervals stored in the set are kept sorted and normalized:
+intervals that overlap or touch each other are merged and empty intervals are discarded.
+
+Sets support the full boolean algebra: union (`|`), intersection (`&`),
+difference (`-`) and symmetric difference (`^`).
+All of them are calculated by a single sweep over both sets, so their complexity is O(n+m).
+
+# Example
+```
+use advanced_collections::interval::{Interval, IntervalSet};
+use std::iter::FromIterator;
+
+fn main() {
+    //overlapping intervals get merged
+    let a = IntervalSet::from_iter(vec![Interval::closed(1, 5), Interval::closed(3, 8)]);
+    assert_eq!(a.as_slice(), &[Interval::closed(1, 8)]);
+
+    let b = IntervalSet::from_iter(vec![Interval::open(2, 4), Interval::closed(6, 10)]);
+
+    assert_eq!((&a | &b).as_slice(), &[Interval::closed(1, 10)]);
+    assert_eq!((&a & &b).as_slice(), &[Interval::open(2, 4), Interval::closed(6, 8)]);
+    assert_eq!((&a - &b).as_slice(), &[Interval::closed(1, 2), Interval::lower_closed(4, 6)]);
+    assert_eq!((&a ^ &b).as_slice(), &[
+        Interval::closed(1, 2),
+        Interval::lower_closed(4, 6),
+        Interval::upper_closed(8, 10)
+    ]);
+
+    assert!(a.contains_val(&7));
+    assert!(!(&a - &b).contains_val(&3));
+}
+```
+*/
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> where T: Ord {
+    //sorted, non-empty and separated from each other
+    intervals: Vec<Interval<T>>
+}
+
+impl<T> IntervalSet<T> where T: Ord {
+
+    /**
+    Creates a new, empty `IntervalSet`.
+
+    # Example
+    ```
+    use advanced_collections::interval::IntervalSet;
+    fn main() {
+        let s: IntervalSet<i32> = IntervalSet::new();
+        assert!(s.is_empty());
+    }
+    ```
+    */
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new()
+        }
+    }
+
+    /**
+    Returns the number of disjoint intervals in the set.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let s = IntervalSet::from_iter(vec![Interval::closed(1, 2), Interval::closed(4, 5)]);
+        assert_eq!(s.len(), 2);
+    }
+    ```
+    */
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /**
+    Checks if the set is empty.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let s = IntervalSet::from_iter(vec![Interval::<i32>::empty()]);
+        assert!(s.is_empty());
+    }
+    ```
+    */
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /**
+    Returns the sorted disjoint intervals of the set as a slice.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let s = IntervalSet::from_iter(vec![Interval::closed(4, 5), Interval::closed(1, 2)]);
+        assert_eq!(s.as_slice(), &[Interval::closed(1, 2), Interval::closed(4, 5)]);
+    }
+    ```
+    */
+    pub fn as_slice(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    /**
+    Returns an iterator over the sorted disjoint intervals of the set.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let s = IntervalSet::from_iter(vec![Interval::closed(1, 2), Interval::closed(2, 5)]);
+        assert_eq!(s.iter().next(), Some(&Interval::closed(1, 5)));
+    }
+    ```
+    */
+    pub fn iter(&self) -> SliceIter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /**
+    Destructs the set and returns its sorted disjoint intervals.
+    */
+    pub fn into_vec(self) -> Vec<Interval<T>> {
+        self.intervals
+    }
+
+    /**
+    Checks if the set contains the given value.
+
+    **Complexity:** O(log n)
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let s = IntervalSet::from_iter(vec![Interval::closed(1, 2), Interval::open(4, 6)]);
+        assert!(s.contains_val(&5));
+        assert!(!s.contains_val(&4));
+    }
+    ```
+    */
+    pub fn contains_val(&self, val: &T) -> bool {
+        self.intervals.find_containing(val).is_some()
+    }
+
+    /**
+    Adds an interval to the set, merging it with intervals that it overlaps or touches.
+
+    **Complexity:** O(n)
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    fn main() {
+        let mut s = IntervalSet::new();
+        s.insert(Interval::closed(1, 2));
+        s.insert(Interval::closed(5, 6));
+        s.insert(Interval::closed(2, 5));
+        assert_eq!(s.as_slice(), &[Interval::closed(1, 6)]);
+    }
+    ```
+    */
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let current = ::std::mem::take(&mut self.intervals);
+        self.intervals = Self::union_sorted(current, vec![interval]);
+    }
+
+    /**
+    Returns a set containing values belonging to any of the sets.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let a = IntervalSet::from_iter(vec![Interval::closed(1, 3)]);
+        let b = IntervalSet::from_iter(vec![Interval::open(3, 5)]);
+        assert_eq!(a.union(&b).as_slice(), &[Interval::lower_closed(1, 5)]);
+    }
+    ```
+    */
+    pub fn union(&self, other: &Self) -> Self where T: Clone {
+        Self {
+            intervals: Self::union_sorted(self.intervals.clone(), other.intervals.clone())
+        }
+    }
+
+    /**
+    Returns a set containing values belonging to both sets.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let a = IntervalSet::from_iter(vec![Interval::closed(1, 3), Interval::closed(5, 7)]);
+        let b = IntervalSet::from_iter(vec![Interval::closed(2, 6)]);
+        assert_eq!(a.intersection(&b).as_slice(), &[Interval::closed(2, 3), Interval::closed(5, 6)]);
+    }
+    ```
+    */
+    pub fn intersection(&self, other: &Self) -> Self where T: Clone {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            let common = a[i].clone().into_intersection(b[j].clone());
+            if !common.is_empty() {
+                result.push(common);
+            }
+            //move forward the interval that ends first
+            if a[i].upper() <= b[j].upper() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self {
+            intervals: result
+        }
+    }
+
+    /**
+    Returns a set containing values belonging to this set but not to the other one.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let a = IntervalSet::from_iter(vec![Interval::closed(1, 9)]);
+        let b = IntervalSet::from_iter(vec![Interval::closed(3, 4), Interval::lower_closed(6, 7)]);
+        assert_eq!(a.difference(&b).as_slice(), &[
+            Interval::lower_closed(1, 3),
+            Interval::open(4, 6),
+            Interval::closed(7, 9)
+        ]);
+    }
+    ```
+    */
+    pub fn difference(&self, other: &Self) -> Self where T: Clone {
+        Self {
+            intervals: Self::difference_sorted(&self.intervals, &other.intervals)
+        }
+    }
+
+    /**
+    Returns a set containing values belonging to exactly one of the sets.
+
+    # Example
+    ```
+    use advanced_collections::interval::{Interval, IntervalSet};
+    use std::iter::FromIterator;
+    fn main() {
+        let a = IntervalSet::from_iter(vec![Interval::closed(1, 5)]);
+        let b = IntervalSet::from_iter(vec![Interval::closed(3, 7)]);
+        assert_eq!(a.symmetric_difference(&b).as_slice(), &[
+            Interval::lower_closed(1, 3),
+            Interval::upper_closed(5, 7)
+        ]);
+    }
+    ```
+    */
+    pub fn symmetric_difference(&self, other: &Self) -> Self where T: Clone {
+        let left = Self::difference_sorted(&self.intervals, &other.intervals);
+        let right = Self::difference_sorted(&other.intervals, &self.intervals);
+        Self {
+            intervals: Self::union_sorted(left, right)
+        }
+    }
+
+//private helpers
+
+    //merges two sorted lists of intervals and coalesces overlapping ones
+    fn union_sorted(a: Vec<Interval<T>>, b: Vec<Interval<T>>) -> Vec<Interval<T>> {
+        let mut result: Vec<Interval<T>> = Vec::with_capacity(a.len() + b.len());
+        let mut a = a.into_iter().peekable();
+        let mut b = b.into_iter().peekable();
+        loop {
+            let take_a = match (a.peek(), b.peek()) {
+                (None, None) => break,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (Some(x), Some(y)) => x.lower() <= y.lower()
+            };
+            let next = if take_a { a.next() } else { b.next() }.unwrap();
+            Self::push_merged(&mut result, next);
+        }
+        result
+    }
+
+    fn difference_sorted(a: &[Interval<T>], b: &[Interval<T>]) -> Vec<Interval<T>> where T: Clone {
+        let mut result = Vec::with_capacity(a.len());
+        let mut j = 0;
+        for interval in a {
+            let mut rest = interval.clone();
+            //intervals from b that end before the current one cannot affect any next one
+            while j < b.len() && b[j] < rest {
+                j += 1;
+            }
+            while j < b.len() && !rest.is_empty() && b[j].intersects(&rest) {
+                let (lo, up) = rest.into_bounds().unwrap();
+                let (b_lo, b_up) = b[j].bounds().unwrap();
+                let left = Interval::from_bounds_or_empty(lo, below(b_lo));
+                if !left.is_empty() {
+                    result.push(left);
+                }
+                let extends_further = *b_up > up;
+                rest = Interval::from_bounds_or_empty(above(b_up), up);
+                if extends_further {
+                    //the subtracted interval may also overlap the next interval
+                    break;
+                }
+                j += 1;
+            }
+            if !rest.is_empty() {
+                result.push(rest);
+            }
+        }
+        result
+    }
+
+    fn push_merged(result: &mut Vec<Interval<T>>, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        if let Some(last) = result.last_mut() {
+            if let Err(interval) = last.merge(interval) {
+                result.push(interval);
+            }
+            return;
+        }
+        result.push(interval);
+    }
+}
+
+//the upper bound that contains all values less than the given lower bound
+fn below<T>(lo: &LowerBound<T>) -> UpperBound<T> where T: Ord + Clone {
+    UpperBound::new(lo.val().clone(), !lo.is_closed())
+}
+
+//the lower bound that contains all values greater than the given upper bound
+fn above<T>(up: &UpperBound<T>) -> LowerBound<T> where T: Ord + Clone {
+    LowerBound::new(up.val().clone(), !up.is_closed())
+}
+
+impl<T> Default for IntervalSet<T> where T: Ord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalSet<T> where T: Ord {
+    ///Creates a normalized set from the provided intervals.
+    fn from_iter<I: IntoIterator<Item=Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by(|a, b| a.lower().cmp(&b.lower()));
+        let mut result = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            Self::push_merged(&mut result, interval);
+        }
+        Self {
+            intervals: result
+        }
+    }
+}
+
+impl<T> Extend<Interval<T>> for IntervalSet<T> where T: Ord {
+    ///Adds all provided intervals to the set.
+    fn extend<I: IntoIterator<Item=Interval<T>>>(&mut self, iter: I) {
+        let other = Self::from_iter(iter);
+        let current = ::std::mem::take(&mut self.intervals);
+        self.intervals = Self::union_sorted(current, other.intervals);
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> where T: Ord {
+    type Item = Interval<T>;
+    type IntoIter = VecIntoIter<Interval<T>>;
+
+    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> where T: Ord {
+    type Item = &'a Interval<T>;
+    type IntoIter = SliceIter<'a, Interval<T>>;
+
+    fn into_iter(self) -> <Self as IntoIterator>::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+//Boolean algebra operators -----------------------------------------------------------------------
+
+impl<T> BitOr for IntervalSet<T> where T: Ord {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> <Self as BitOr>::Output {
+        Self {
+            intervals: Self::union_sorted(self.intervals, rhs.intervals)
+        }
+    }
+}
+
+impl<T> BitOr for &IntervalSet<T> where T: Ord + Clone {
+    type Output = IntervalSet<T>;
+
+    fn bitor(self, rhs: Self) -> <Self as BitOr>::Output {
+        self.union(rhs)
+    }
+}
+
+impl<T> BitAnd for IntervalSet<T> where T: Ord + Clone {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> <Self as BitAnd>::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl<T> BitAnd for &IntervalSet<T> where T: Ord + Clone {
+    type Output = IntervalSet<T>;
+
+    fn bitand(self, rhs: Self) -> <Self as BitAnd>::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl<T> Sub for IntervalSet<T> where T: Ord + Clone {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> <Self as Sub>::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl<T> Sub for &IntervalSet<T> where T: Ord + Clone {
+    type Output = IntervalSet<T>;
+
+    fn sub(self, rhs: Self) -> <Self as Sub>::Output {
+        self.difference(rhs)
+    }
+}
+
+impl<T> BitXor for IntervalSet<T> where T: Ord + Clone {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> <Self as BitXor>::Output {
+        self.symmetric_difference(&rhs)
+    }
+}
+
+impl<T> BitXor for &IntervalSet<T> where T: Ord + Clone {
+    type Output = IntervalSet<T>;
+
+    fn bitxor(self, rhs: Self) -> <Self as BitXor>::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: Vec<Interval<i32>>) -> IntervalSet<i32> {
+        IntervalSet::from_iter(intervals)
+    }
+
+    #[test]
+    fn test_normalization(){
+        let s = set(vec![
+            Interval::open(5, 7),
+            Interval::empty(),
+            Interval::closed(1, 2),
+            Interval::closed(7, 8),
+            Interval::open(2, 3),
+            Interval::open(10, 11)
+        ]);
+        assert_eq!(s.as_slice(), &[Interval::lower_closed(1, 3), Interval::upper_closed(5, 8), Interval::open(10, 11)]);
+        //open bounds with the same value are not merged
+        let s = set(vec![Interval::open(1, 2), Interval::open(2, 3)]);
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_contains_val(){
+        let s = set(vec![Interval::closed(1, 2), Interval::open(4, 6), Interval::single(8)]);
+        for &(v, exp) in &[(0, false), (1, true), (2, true), (3, false), (4, false), (5, true), (6, false), (8, true), (9, false)] {
+            assert_eq!(s.contains_val(&v), exp, "value {}", v);
+        }
+        assert!(!IntervalSet::new().contains_val(&1));
+    }
+
+    #[test]
+    fn test_insert_extend(){
+        let mut s = IntervalSet::new();
+        s.insert(Interval::closed(10, 12));
+        s.insert(Interval::closed(1, 2));
+        s.insert(Interval::empty());
+        assert_eq!(s.len(), 2);
+        s.extend(vec![Interval::closed(2, 4), Interval::closed(5, 10)]);
+        assert_eq!(s.as_slice(), &[Interval::closed(1, 4), Interval::closed(5, 12)]);
+    }
+
+    #[test]
+    fn test_union(){
+        let a = set(vec![Interval::closed(1, 2), Interval::closed(6, 7)]);
+        let b = set(vec![Interval::open(2, 3), Interval::closed(4, 5), Interval::closed(7, 9)]);
+        let exp = [Interval::lower_closed(1, 3), Interval::closed(4, 5), Interval::closed(6, 9)];
+        assert_eq!((&a | &b).as_slice(), &exp);
+        assert_eq!((a | b).as_slice(), &exp);
+    }
+
+    #[test]
+    fn test_intersection(){
+        let a = set(vec![Interval::closed(1, 4), Interval::closed(6, 10)]);
+        let b = set(vec![Interval::open(0, 2), Interval::closed(3, 7), Interval::open(9, 12)]);
+        let exp = [Interval::lower_closed(1, 2), Interval::closed(3, 4), Interval::closed(6, 7), Interval::upper_closed(9, 10)];
+        assert_eq!((&a & &b).as_slice(), &exp);
+        assert_eq!((a & b).as_slice(), &exp);
+        let e: IntervalSet<i32> = IntervalSet::new();
+        assert!((&e & &set(vec![Interval::closed(1, 2)])).is_empty());
+    }
+
+    #[test]
+    fn test_difference(){
+        let a = set(vec![Interval::closed(1, 10), Interval::closed(20, 30)]);
+        let b = set(vec![Interval::open(0, 2), Interval::single(5), Interval::open(8, 22), Interval::closed(25, 26)]);
+        let exp = [
+            Interval::lower_closed(2, 5),
+            Interval::upper_closed(5, 8),
+            Interval::lower_closed(22, 25),
+            Interval::upper_closed(26, 30)
+        ];
+        assert_eq!((&a - &b).as_slice(), &exp);
+        assert_eq!((a.clone() - b.clone()).as_slice(), &exp);
+        assert!((&b - &b).is_empty());
+        assert_eq!(&a - &IntervalSet::new(), a);
+        //subtracting a superset removes everything
+        assert!((&a - &set(vec![Interval::closed(0, 40)])).is_empty());
+    }
+
+    #[test]
+    fn test_symmetric_difference(){
+        let a = set(vec![Interval::closed(1, 5), Interval::closed(10, 15)]);
+        let b = set(vec![Interval::closed(5, 10)]);
+        let exp = [Interval::lower_closed(1, 5), Interval::open(5, 10), Interval::upper_closed(10, 15)];
+        assert_eq!((&a ^ &b).as_slice(), &exp);
+        assert_eq!((a.clone() ^ b).as_slice(), &exp);
+        assert!((&a ^ &a).is_empty());
+    }
+}
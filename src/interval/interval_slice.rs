@@ -0,0 +1,75 @@
+use super::interval::Interval;
+
+/**
+Algorithms for slices of intervals.
+
+Most of them require intervals to be sorted and disjoint, which can be verified
+using `is_sorted_disjoint()`. `IntervalSet` always keeps its intervals in this form.
+
+# Example
+```
+use advanced_collections::interval::{Interval, IntervalSlice};
+fn main() {
+    let buckets = [Interval::lower_closed(0, 10), Interval::lower_closed(10, 100), Interval::closed(200, 300)];
+    assert!(buckets.is_sorted_disjoint());
+    assert_eq!(buckets.find_containing(&10), Some(1));
+    assert_eq!(buckets.find_containing(&150), None);
+}
+```
+*/
+pub trait IntervalSlice<T> where T: Ord {
+    /**
+    Finds the index of the interval that contains the given value.
+
+    The slice needs to be sorted and disjoint, otherwise the result is unspecified.
+
+    **Complexity:** O(log n)
+    */
+    fn find_containing(&self, val: &T) -> Option<usize>;
+
+    /**
+    Checks if intervals are non-empty, sorted and do not have any common values.
+    */
+    fn is_sorted_disjoint(&self) -> bool;
+}
+
+impl<T> IntervalSlice<T> for [Interval<T>] where T: Ord {
+    fn find_containing(&self, val: &T) -> Option<usize> {
+        //the first interval that does not end before the value
+        let idx = self.partition_point(|i| i < val);
+        match self.get(idx) {
+            Some(i) if i.contains_val(val) => Some(idx),
+            _ => None
+        }
+    }
+
+    fn is_sorted_disjoint(&self) -> bool {
+        self.iter().all(|i| !i.is_empty()) && self.windows(2).all(|w| w[0] < w[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_containing(){
+        let s = [Interval::open(1, 3), Interval::closed(3, 5), Interval::single(7), Interval::lower_closed(8, 9)];
+        let expected = [(1, None), (2, Some(0)), (3, Some(1)), (5, Some(1)), (6, None), (7, Some(2)), (8, Some(3)), (9, None)];
+        for &(val, idx) in &expected {
+            assert_eq!(s.find_containing(&val), idx, "value {}", val);
+        }
+        let empty: [Interval<i32>; 0] = [];
+        assert_eq!(empty.find_containing(&1), None);
+    }
+
+    #[test]
+    fn test_is_sorted_disjoint(){
+        assert!([Interval::open(1, 3), Interval::closed(3, 5)].is_sorted_disjoint());
+        assert!(![Interval::closed(1, 3), Interval::closed(3, 5)].is_sorted_disjoint());
+        assert!(![Interval::closed(4, 5), Interval::closed(1, 2)].is_sorted_disjoint());
+        assert!(![Interval::closed(1, 2), Interval::empty()].is_sorted_disjoint());
+        let empty: [Interval<i32>; 0] = [];
+        assert!(empty.is_sorted_disjoint());
+    }
+}
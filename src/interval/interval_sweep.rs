@@ -0,0 +1,125 @@
+use std::ops::Sub;
+use super::interval::Interval;
+
+impl<T> Interval<T> where T: Ord {
+    /**
+    Merges all overlapping and touching intervals.
+
+    Returns sorted and disjoint intervals that contain the same values as the provided ones.
+    Empty intervals are skipped.
+
+    **Complexity:** O(n log n)
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let merged = Interval::merge_all(vec![Interval::closed(5, 7), Interval::lower_closed(1, 3), Interval::closed(3, 4)]);
+        assert_eq!(merged, vec![Interval::closed(1, 4), Interval::closed(5, 7)]);
+    }
+    ```
+    */
+    pub fn merge_all<I>(intervals: I) -> Vec<Self> where I: IntoIterator<Item=Self> {
+        Self::merge_all_when(intervals, |_, _| false)
+    }
+
+    /**
+    Merges all intervals that overlap or are separated by a gap not larger than the tolerance.
+
+    Merged intervals span the gaps between the original ones.
+    The result is sorted and empty intervals are skipped.
+
+    **Complexity:** O(n log n)
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        //bookings in minutes since midnight, separated by less than 5 minutes form one session
+        let bookings = vec![Interval::closed(600, 630), Interval::closed(633, 660), Interval::closed(700, 720)];
+        let sessions = Interval::merge_all_with_tolerance(bookings, 5);
+        assert_eq!(sessions, vec![Interval::closed(600, 660), Interval::closed(700, 720)]);
+    }
+    ```
+    */
+    pub fn merge_all_with_tolerance<I>(intervals: I, gap_tolerance: T) -> Vec<Self>
+        where I: IntoIterator<Item=Self>, T: Clone + Sub<Output=T>
+    {
+        Self::merge_all_when(intervals, |last, next| {
+            let gap = next.lower().unwrap().val().clone() - last.upper().unwrap().val().clone();
+            gap <= gap_tolerance
+        })
+    }
+
+    /**
+    Returns the smallest interval that contains all provided intervals.
+
+    Empty intervals are skipped and an empty interval is returned if there are no other ones.
+
+    **Complexity:** O(n)
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    fn main() {
+        let all = Interval::span_all(vec![Interval::open(4, 6), Interval::empty(), Interval::closed(1, 2)]);
+        assert_eq!(all, Interval::lower_closed(1, 6));
+        assert!(Interval::<i32>::span_all(Vec::new()).is_empty());
+    }
+    ```
+    */
+    pub fn span_all<I>(intervals: I) -> Self where I: IntoIterator<Item=Self> {
+        intervals.into_iter().fold(Self::empty(), |mut acc, interval| {
+            acc.span(interval);
+            acc
+        })
+    }
+
+    //merges sorted intervals that can be merged or that are separated and satisfy the predicate
+    fn merge_all_when<I, F>(intervals: I, mut join_separated: F) -> Vec<Self>
+        where I: IntoIterator<Item=Self>, F: FnMut(&Self, &Self) -> bool
+    {
+        let mut intervals: Vec<Self> = intervals.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by(|a, b| a.lower().cmp(&b.lower()));
+        let mut result: Vec<Self> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match result.last_mut() {
+                Some(last) if last.can_be_merged(&interval) || join_separated(last, &interval) => last.span(interval),
+                _ => result.push(interval)
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_all(){
+        assert!(Interval::<i32>::merge_all(Vec::new()).is_empty());
+        assert_eq!(Interval::merge_all(vec![Interval::empty(), Interval::open(1, 2)]), vec![Interval::open(1, 2)]);
+        //open bounds at the same value leave a gap
+        assert_eq!(Interval::merge_all(vec![Interval::open(2, 3), Interval::open(1, 2)]), vec![Interval::open(1, 2), Interval::open(2, 3)]);
+        assert_eq!(Interval::merge_all(vec![Interval::closed(1, 10), Interval::closed(2, 3), Interval::upper_closed(10, 11)]), vec![Interval::closed(1, 11)]);
+    }
+
+    #[test]
+    fn test_merge_all_with_tolerance(){
+        let intervals = vec![Interval::closed(0, 1), Interval::closed(3, 4), Interval::open(7, 8), Interval::closed(2, 2)];
+        assert_eq!(Interval::merge_all_with_tolerance(intervals.clone(), 0), vec![Interval::closed(0, 1), Interval::closed(2, 2), Interval::closed(3, 4), Interval::open(7, 8)]);
+        assert_eq!(Interval::merge_all_with_tolerance(intervals.clone(), 2), vec![Interval::closed(0, 4), Interval::open(7, 8)]);
+        assert_eq!(Interval::merge_all_with_tolerance(intervals.clone(), 3), vec![Interval::lower_closed(0, 8)]);
+        let open = vec![Interval::open(0, 1), Interval::open(1, 2)];
+        assert_eq!(Interval::merge_all_with_tolerance(open, 0), vec![Interval::open(0, 2)]);
+    }
+
+    #[test]
+    fn test_span_all(){
+        assert_eq!(Interval::span_all(vec![Interval::single(3)]), Interval::single(3));
+        assert_eq!(Interval::span_all(vec![Interval::closed(1, 5), Interval::open(2, 3)]), Interval::closed(1, 5));
+        assert_eq!(Interval::span_all(vec![Interval::upper_closed(1, 3), Interval::lower_closed(1, 3)]), Interval::closed(1, 3));
+        assert!(Interval::span_all(vec![Interval::<i32>::empty(), Interval::empty()]).is_empty());
+    }
+}
@@ -24,6 +24,26 @@ A special case of an interval is an empty interval, usually noted as:
 
 **More:** <https://en.wikipedia.org/wiki/Interval_(mathematics>
 
+# Compile time intervals
+
+Intervals of primitive integers can be created in const context
+using `const_`-prefixed constructors.
+The type of the interval needs to be specified explicitly:
+
+```
+use advanced_collections::interval::Interval;
+
+static BUCKETS: [Interval<u32>; 3] = [
+    Interval::<u32>::const_lower_closed(0, 10),
+    Interval::<u32>::const_lower_closed(10, 100),
+    Interval::<u32>::const_closed(100, 1000)
+];
+
+fn main() {
+    assert!(BUCKETS[1].contains_val(&50));
+}
+```
+
 # Inspiration
 
 This implementation is highly inspired by three C++ boost libraries:
@@ -39,6 +59,22 @@ mod bounds;
 mod interval;
 mod interval_cmp;
 mod interval_math;
+mod interval_const;
+mod interval_conv;
+mod interval_parse;
+mod interval_parser;
+mod interval_set;
+mod interval_slice;
+mod interval_sweep;
+#[cfg(feature = "std-time")]
+mod time;
+#[cfg(feature = "num-traits")]
+mod checked;
 
 pub use self::bounds::{LowerBound, UpperBound};
-pub use self::interval::Interval;
\ No newline at end of file
+pub use self::interval::{Interval, IntervalKind, DisplayCompact};
+pub use self::interval_conv::InvalidBoundsError;
+pub use self::interval_parse::{ParseIntervalError, INTERVAL_GRAMMAR};
+pub use self::interval_parser::IntervalParser;
+pub use self::interval_set::IntervalSet;
+pub use self::interval_slice::IntervalSlice;
\ No newline at end of file
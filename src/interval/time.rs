@@ -0,0 +1,86 @@
+use std::ops::Sub;
+use std::time::Duration;
+use super::interval::Interval;
+
+/*
+Helpers for creating time windows relative to the current time.
+They work with any time type that supports subtraction of a Duration,
+such as std::time::Instant and std::time::SystemTime.
+*/
+impl<T> Interval<T> where T: Ord {
+    /**
+    Creates a time window that covers the given duration before `now`: `[now - duration, now]`.
+
+    Panics if `now - duration` cannot be represented by the time type.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    use std::time::{Duration, Instant};
+    fn main() {
+        let now = Instant::now();
+        let window = Interval::last(Duration::from_secs(60), now);
+        assert!(window.contains_val(&now));
+        assert!(window.contains_val(&(now - Duration::from_secs(60))));
+        assert!(!window.contains_val(&(now - Duration::from_secs(61))));
+    }
+    ```
+    */
+    pub fn last(duration: Duration, now: T) -> Self where T: Clone + Sub<Duration, Output=T> {
+        Self::closed(now.clone() - duration, now)
+    }
+
+    /**
+    Creates a time window that lasts from `now` to the given deadline: `[now, deadline)`.
+
+    The deadline itself does not belong to the window.
+    Returns an empty interval if the deadline has already passed.
+
+    # Example
+    ```
+    use advanced_collections::interval::Interval;
+    use std::time::{Duration, Instant};
+    fn main() {
+        let now = Instant::now();
+        let deadline = now + Duration::from_secs(5);
+        let window = Interval::until(deadline, now);
+        assert!(window.contains_val(&now));
+        assert!(!window.contains_val(&deadline));
+        assert!(Interval::until(now, deadline).is_empty());
+    }
+    ```
+    */
+    pub fn until(deadline: T, now: T) -> Self {
+        if deadline <= now {
+            Self::empty()
+        } else {
+            Self::lower_closed(now, deadline)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_last(){
+        let now = Instant::now();
+        let i = Interval::last(Duration::from_millis(100), now);
+        assert_eq!(i, Interval::closed(now - Duration::from_millis(100), now));
+        let i = Interval::last(Duration::from_secs(0), now);
+        assert!(i.is_single());
+        let epoch = UNIX_EPOCH + Duration::from_secs(1000);
+        assert_eq!(Interval::last(Duration::from_secs(10), epoch), Interval::closed(UNIX_EPOCH + Duration::from_secs(990), epoch));
+    }
+
+    #[test]
+    fn test_until(){
+        let now = SystemTime::now();
+        let deadline = now + Duration::from_secs(1);
+        assert_eq!(Interval::until(deadline, now), Interval::lower_closed(now, deadline));
+        assert!(Interval::until(now, now).is_empty());
+        assert!(Interval::until(now, deadline).is_empty());
+    }
+}
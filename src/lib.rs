@@ -14,9 +14,30 @@ Cargo.toml:
 advanced_collections = "0.1"
 ```
 
+# Features
+
+- `std` (enabled by default) - collections that need the standard library. Without it only
+  the stack-allocated `ArrayCircularBuffer` is available and the crate works in `no_std` environments.
+- `rayon` - parallel construction of collections and parallel iteration over circular buffers
+  using the [rayon](https://crates.io/crates/rayon) crate.
+- `std-time` - helpers for creating intervals of `std::time` types relative to the current time.
+- `bytes` - `Buf` and `BufMut` implementations of `CircularBuffer<u8>` from the
+  [bytes](https://crates.io/crates/bytes) crate.
+- `num-traits` - overflow-checked interval math for types implementing checked operations
+  from the [num-traits](https://crates.io/crates/num-traits) crate.
+- `test-util` - model-based testing utilities for checking collections against reference implementations.
+
 */
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "std")]
 pub mod counter;
+#[cfg(feature = "std")]
 pub mod disjoint_set;
 pub mod circular_buffer;
-pub mod interval;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod interval;
+pub mod prelude;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
@@ -0,0 +1,35 @@
+/*!
+The most commonly used types and traits of the crate.
+
+Glob-import the prelude to use them without spelling module paths:
+
+```
+use advanced_collections::prelude::*;
+
+fn main(){
+    let mut cb = CircularBuffer::new(2);
+    cb.extend(1..4);
+    let c: Counter<i32> = cb.iter().cloned().collect();
+    assert_eq!(c.len(), 2);
+
+    let mut ds: DisjointSet<i32> = DisjointSet::new();
+    ds.union(1, 2);
+    assert!(ds.in_union(&1, &2));
+
+    let buckets = [Interval::lower_closed(0, 10), Interval::closed(10, 20)];
+    assert_eq!(buckets.find_containing(&10), Some(1));
+}
+```
+
+Only `ArrayCircularBuffer` and `GenericCircularBuffer` are available without the `std` feature.
+*/
+
+pub use crate::circular_buffer::{ArrayCircularBuffer, GenericCircularBuffer, EvictionPolicy};
+#[cfg(feature = "std")]
+pub use crate::circular_buffer::CircularBuffer;
+#[cfg(feature = "std")]
+pub use crate::counter::{Counter, FastCounter, CounterSink};
+#[cfg(feature = "std")]
+pub use crate::disjoint_set::{DisjointSet, FastDisjointSet};
+#[cfg(feature = "std")]
+pub use crate::interval::{Interval, IntervalSet, IntervalSlice, LowerBound, UpperBound};
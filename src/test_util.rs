@@ -0,0 +1,51 @@
+/*!
+Utilities shared by the model-based tests of all collections.
+
+Test harnesses of the collections are available in their `test_util` modules.
+This module is available for tests of this crate and with the `test-util` feature.
+*/
+
+/**
+A small, deterministic pseudo-random number generator (xorshift64*).
+
+It is good enough for generating test cases and makes failures reproducible from a seed.
+*/
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64
+}
+
+impl XorShift {
+    ///Creates a new generator. Seed 0 is replaced with a non-zero constant.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed }
+        }
+    }
+
+    ///Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    ///Returns a pseudo-random number from range `0..bound`. Panics if bound is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xorshift_deterministic(){
+        let a: Vec<u64> = (0..5).scan(XorShift::new(7), |r, _| Some(r.next_u64())).collect();
+        let b: Vec<u64> = (0..5).scan(XorShift::new(7), |r, _| Some(r.next_u64())).collect();
+        assert_eq!(a, b);
+        assert!(XorShift::new(0).next_u64() != 0);
+    }
+}